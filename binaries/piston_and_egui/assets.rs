@@ -0,0 +1,143 @@
+use anyhow::{Context, Result};
+use async_chess_client::prelude::ChessPiece;
+use find_folder::Search;
+use glutin::window::Icon;
+use std::path::Path;
+
+///All of the assets needed which aren't chess pieces
+pub const EXTRA_ASSETS: [&str; 4] = [
+    "board_alt.png",
+    "highlight.png",
+    "selected.png",
+    "board_updated.png",
+];
+
+///The asset to use for the window icon - already in the [`assets_manifest`] as a piece sprite
+pub const ICON_ASSET: &str = "white_knight.png";
+
+///Gets the file names of every asset the game needs - one for each of [`ChessPiece::all_variants`], then all of the [`EXTRA_ASSETS`]
+pub fn assets_manifest() -> Vec<String> {
+    ChessPiece::all_variants()
+        .into_iter()
+        .map(ChessPiece::to_file_name)
+        .chain(EXTRA_ASSETS.into_iter().map(String::from))
+        .collect()
+}
+
+///Loads the [`ICON_ASSET`] from the assets folder as a window icon
+///
+/// # Errors
+/// - Fail to find the assets folder
+/// - Fail to open or decode the [`ICON_ASSET`]
+/// - Fail to turn the image into an [`Icon`]
+pub fn load_icon() -> Result<Icon> {
+    let folder = Search::ParentsThenKids(3, 3)
+        .for_folder("assets")
+        .context("finding assets folder")?;
+    let image = image::open(folder.join(ICON_ASSET))
+        .with_context(|| format!("opening {ICON_ASSET}"))?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+
+    Icon::from_rgba(image.into_raw(), width, height).context("making icon")
+}
+
+///Checks that every asset in the [`assets_manifest`] exists in the assets folder and can be decoded as an image, without uploading anything to the GPU
+///
+/// # Errors
+/// Returns a description of every problem found - if the assets folder can't be found, that is the only problem
+pub fn verify_assets() -> Result<(), Vec<String>> {
+    let folder = Search::ParentsThenKids(3, 3)
+        .for_folder("assets")
+        .map_err(|e| vec![format!("unable to find assets folder: {e}")])?;
+
+    verify_assets_in(&folder)
+}
+
+///Checks that every asset in the [`assets_manifest`] exists in `folder` and can be decoded as an image, as [`verify_assets`] does for the assets folder
+///
+/// # Errors
+/// Returns a description of every problem found
+pub fn verify_assets_in(folder: &Path) -> Result<(), Vec<String>> {
+    let problems: Vec<String> = assets_manifest()
+        .into_iter()
+        .filter_map(|asset| {
+            let path = folder.join(&asset);
+            if !path.is_file() {
+                Some(format!("{asset} is missing from {folder:?}"))
+            } else if let Err(e) = image::open(&path) {
+                Some(format!("{asset} isn't a valid image: {e}"))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
+#[cfg(test)]
+///Tests that the [`assets_manifest`] covers every piece
+mod tests {
+    use super::{assets_manifest, verify_assets, verify_assets_in, EXTRA_ASSETS};
+    use async_chess_client::prelude::ChessPiece;
+    use find_folder::Search;
+    use std::fs;
+
+    ///Every [`ChessPiece::all_variants`] needs its sprite in the manifest
+    #[test]
+    fn manifest_has_every_piece() {
+        let manifest = assets_manifest();
+        for piece in ChessPiece::all_variants() {
+            let file_name = piece.to_file_name();
+            assert!(
+                manifest.contains(&file_name),
+                "{file_name} for {piece:?} is missing from the assets manifest"
+            );
+        }
+        assert_eq!(
+            manifest.len(),
+            ChessPiece::all_variants().len() + EXTRA_ASSETS.len()
+        );
+    }
+
+    ///Everything in the manifest is in the assets folder, and decodes
+    #[test]
+    fn assets_folder_matches_manifest() {
+        if let Err(problems) = verify_assets() {
+            panic!("problems with assets:\n{}", problems.join("\n"));
+        }
+    }
+
+    ///An empty sprite and a missing one are both reported, by name, from a copy of the assets folder
+    #[test]
+    fn broken_and_missing_assets_are_reported() {
+        let assets = Search::ParentsThenKids(3, 3).for_folder("assets").unwrap();
+        let folder =
+            std::env::temp_dir().join(format!("async_chess_assets_{}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        for asset in assets_manifest() {
+            fs::copy(assets.join(&asset), folder.join(&asset)).unwrap();
+        }
+        assert_eq!(verify_assets_in(&folder), Ok(()));
+
+        fs::write(folder.join("white_pawn.png"), []).unwrap();
+        fs::remove_file(folder.join("highlight.png")).unwrap();
+        let problems = verify_assets_in(&folder).unwrap_err();
+        fs::remove_dir_all(&folder).unwrap();
+
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(
+            problems[0].starts_with("white_pawn.png isn't a valid image"),
+            "{problems:?}"
+        );
+        assert!(
+            problems[1].starts_with("highlight.png is missing"),
+            "{problems:?}"
+        );
+    }
+}
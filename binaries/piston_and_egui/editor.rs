@@ -0,0 +1,110 @@
+use async_chess_client::{
+    chess::boards::board::{Board, CanMovePiece, PositionError},
+    prelude::{ChessPiece, Coords},
+};
+
+///State for composing a position by hand. It is kept apart from the game being played, and nothing in it is ever sent to the server
+#[derive(Debug, Default)]
+pub struct SetupEditor {
+    ///The position being composed - starts off empty
+    board: Board<CanMovePiece>,
+    ///The piece last picked from the palette, which gets placed on every square clicked
+    brush: Option<ChessPiece>,
+}
+
+impl SetupEditor {
+    ///Picks a piece from the palette to place with [`Self::place`]
+    pub fn pick(&mut self, piece: ChessPiece) {
+        self.brush = Some(piece);
+    }
+
+    ///Gets the piece which has been picked from the palette, if one has
+    pub fn brush(&self) -> Option<ChessPiece> {
+        self.brush
+    }
+
+    ///Places the picked piece at `coords`, replacing whatever was there. Legality is ignored, and nothing happens if no piece has been picked
+    pub fn place(&mut self, coords: Coords) {
+        if coords.is_on_board() {
+            if let Some(piece) = self.brush {
+                self.board[coords] = Some(piece);
+            }
+        }
+    }
+
+    ///Removes any piece from the square at `coords`
+    pub fn clear(&mut self, coords: Coords) {
+        if coords.is_on_board() {
+            self.board[coords] = None;
+        }
+    }
+
+    ///Gets the position being composed
+    pub fn board(&self) -> &Board<CanMovePiece> {
+        &self.board
+    }
+
+    ///Closes the editor, giving back the composed position along with whether or not it could come from a real game, from [`Board::validate_position`]
+    pub fn finish(self) -> (Board<CanMovePiece>, Result<(), PositionError>) {
+        let validity = self.board.validate_position();
+        (self.board, validity)
+    }
+}
+
+#[cfg(test)]
+///Tests for composing positions with the [`SetupEditor`]
+mod tests {
+    use super::SetupEditor;
+    use async_chess_client::{
+        chess::boards::board::PositionError,
+        prelude::{ChessPiece, ChessPieceKind, Coords},
+    };
+
+    ///Placing before anything is picked does nothing
+    #[test]
+    fn place_needs_a_brush() {
+        let mut editor = SetupEditor::default();
+        assert_eq!(editor.brush(), None);
+
+        editor.place(Coords::OnBoard(4, 4));
+        assert_eq!(editor.board()[Coords::OnBoard(4, 4)], None);
+    }
+
+    ///The picked piece is placed on every square clicked, and clearing removes it again
+    #[test]
+    fn pick_place_and_clear() {
+        let rook = ChessPiece {
+            kind: ChessPieceKind::Rook,
+            is_white: false,
+        };
+        let mut editor = SetupEditor::default();
+        editor.pick(rook);
+        assert_eq!(editor.brush(), Some(rook));
+
+        editor.place(Coords::OnBoard(0, 0));
+        editor.place(Coords::OnBoard(7, 0));
+        assert_eq!(editor.board()[Coords::OnBoard(0, 0)], Some(rook));
+        assert_eq!(editor.board()[Coords::OnBoard(7, 0)], Some(rook));
+
+        editor.clear(Coords::OnBoard(0, 0));
+        assert_eq!(editor.board()[Coords::OnBoard(0, 0)], None);
+        assert_eq!(editor.board()[Coords::OnBoard(7, 0)], Some(rook));
+        assert_eq!(
+            editor.brush(),
+            Some(rook),
+            "clearing shouldn't drop the brush"
+        );
+
+        //off the board is ignored rather than panicking
+        editor.place(Coords::OffBoard);
+        editor.clear(Coords::OffBoard);
+    }
+
+    ///An empty board has no kings, so it isn't a real position
+    #[test]
+    fn empty_board_has_no_kings() {
+        let (board, validity) = SetupEditor::default().finish();
+        assert_eq!(validity, Err(PositionError::WrongKingCount(true, 0)));
+        assert!(board.same_position(SetupEditor::default().board()));
+    }
+}
@@ -7,7 +7,10 @@ use std::{
     fs::{create_dir_all},
 };
 
-use crate::piston::PistonConfig;
+use crate::{
+    keybindings::KeyBinding,
+    piston::{BoardTheme, PistonConfig},
+};
 
 ///Function to start up an [`AsyncChessLauncher`] using [`eframe::run_native`]
 #[tracing::instrument]
@@ -28,6 +31,12 @@ struct AsyncChessLauncher {
     id: String,
     ///The width/height of the to-be-opened window
     res: String,
+    ///The board theme from the existing config, which isn't editable here so it gets kept as-is
+    theme: Option<BoardTheme>,
+    ///The frame rate cap from the existing config, which isn't editable here so it gets kept as-is
+    max_fps: Option<u64>,
+    ///The rebound buttons from the existing config, which aren't editable here so they get kept as-is
+    keybindings: Vec<KeyBinding>,
 }
 
 impl Default for AsyncChessLauncher {
@@ -35,6 +44,9 @@ impl Default for AsyncChessLauncher {
         Self {
             id: "0".into(),
             res: "600".into(),
+            theme: None,
+            max_fps: None,
+            keybindings: vec![],
         }
     }
 }
@@ -45,10 +57,21 @@ impl AsyncChessLauncher {
     ///If `start_uc` is [`Some`], then it uses those values, and if not then it uses the [`AsyncChessLauncher::default`] values - `id: 0, res: 600`
     pub fn new(start_uc: Option<PistonConfig>) -> Self {
         start_uc
-            .map(|PistonConfig { id, res }| Self {
-                id: id.to_string(),
-                res: res.to_string(),
-            })
+            .map(
+                |PistonConfig {
+                     id,
+                     res,
+                     theme,
+                     max_fps,
+                     keybindings,
+                 }| Self {
+                    id: id.to_string(),
+                    res: res.to_string(),
+                    theme,
+                    max_fps,
+                    keybindings,
+                },
+            )
             .unwrap_or_default()
     }
 }
@@ -90,6 +113,9 @@ impl App for AsyncChessLauncher {
             //PANICS - we parse ^
             id: self.id.parse().unwrap(),
             res: self.res.parse().unwrap(),
+            theme: self.theme,
+            max_fps: self.max_fps,
+            keybindings: self.keybindings.clone(),
         };
 
         std::thread::spawn(move || {
@@ -1,6 +1,10 @@
 use crate::{
-    piston::{mp_valid, to_board_pixels},
-    pixel_size_consts::{BOARD_S, BOARD_TILE_S, LEFT_BOUND_PADDING, RIGHT_BOUND, TILE_S},
+    assets::assets_manifest,
+    editor::SetupEditor,
+    piston::{window_title, window_to_square, BoardTheme},
+    pixel_size_consts::{
+        BOARD_S, BOARD_TILE_S, LEFT_BOUND, LEFT_BOUND_PADDING, RIGHT_BOUND, TILE_S,
+    },
 };
 use anyhow::{Context as _, Result};
 use async_chess_client::{
@@ -11,17 +15,33 @@ use async_chess_client::{
         },
         server_interface::{no_connection_list, JSONMove},
     },
-    prelude::{Coords, Either, ErrorExt},
+    prelude::{ChessPiece, ChessPieceKind, Coords, Either, ErrorExt},
     util::{cacher::Cacher, error_ext::ToAnyhowErr},
 };
 use graphics::{DrawState, ImageSize};
-use piston_window::{clear, rectangle::square, Context, G2d, Image, PistonWindow, Transformed};
+use piston_window::{
+    clear, rectangle::square, Context, G2d, Image, Line, PistonWindow, Rectangle, Transformed,
+};
 use std::sync::mpsc::TryRecvError;
 use std::time::Duration;
 use async_chess_client::prelude::DoOnInterval;
 use async_chess_client::util::time_based_structs::do_on_interval::UpdateOnCheck;
 use crate::pixel_size_consts::TOP_SPACE;
 
+///Size in pixels for pieces which have been taken
+const TAKEN_TILE_SIZE: f64 = TILE_S * 0.75;
+///Starting Y for Taken tiles, such that when all pieces are taken, it it centred
+const START_Y: f64 = (BOARD_S - (TAKEN_TILE_SIZE * 16.0)) / 2.0; //16 pieces
+///The pieces shown in the [`SetupEditor`] palette, from top to bottom - white's are drawn where white's taken pieces go, and black's where black's go
+const PALETTE: [ChessPieceKind; 6] = [
+    ChessPieceKind::King,
+    ChessPieceKind::Queen,
+    ChessPieceKind::Rook,
+    ChessPieceKind::Bishop,
+    ChessPieceKind::Knight,
+    ChessPieceKind::Pawn,
+];
+
 ///Struct to hold Game of Chess
 pub struct ChessGame {
     ///The id of the game being played
@@ -37,37 +57,62 @@ pub struct ChessGame {
     ///The refresher for making server requests
     refresher: ListRefresher,
     ///Whenever we get an update, display a message for an interval, timed by this
-    show_board_update: Option<DoOnInterval<UpdateOnCheck>>
+    show_board_update: Option<DoOnInterval<UpdateOnCheck>>,
+    ///Whether or not the board is currently the [`no_connection_list`]
+    no_connection: bool,
+    ///The colours to draw the board with, or `None` to use the board image
+    theme: Option<BoardTheme>,
+    ///Whether or not the board has changed since [`Self::take_changed`] was last called
+    changed: bool,
+    ///The position editor, if it is open - while it is, clicks edit its board instead of making moves
+    editor: Option<SetupEditor>,
 }
 impl ChessGame {
     ///Create a new `ChessGame`f
     ///
     /// # Errors
-    /// - Can fail if the cacher incorrectly populates
-    pub fn new(win: &mut PistonWindow, id: u32) -> Result<Self> {
+    /// - Can fail if the cacher incorrectly populates, or is missing anything from the [`assets_manifest`]
+    pub fn new(win: &mut PistonWindow, id: u32, theme: Option<BoardTheme>) -> Result<Self> {
+        let cache = Cacher::new(win).context("making cacher")?;
+        for asset in assets_manifest() {
+            cache
+                .get(&asset)
+                .with_context(|| format!("cacher doesn't contain {asset:?}"))?;
+        }
+
         Ok(Self {
             id,
-            cache: Cacher::new(win).context("making cacher")?,
+            cache,
             board: BoardContainer::default(),
             refresher: ListRefresher::new(id),
             last_pressed: Coords::OffBoard,
             ex_last_pressed: Coords::OffBoard,
             show_board_update: None,
+            no_connection: false,
+            theme,
+            changed: true,
+            editor: None,
         })
     }
 
-    ///Handles mouse input
+    ///Gets the title the window should have for the current state of the game, using [`window_title`]
+    pub fn title(&self) -> String {
+        window_title(
+            self.id,
+            self.no_connection,
+            matches!(self.board, Either::Right(_)),
+        )
+    }
+
+    ///Handles a click on the board square at `(x, y)` - the first click selects a piece, and the second tries to move it
     ///
     /// # Errors
     /// - Can fail if there is an error sending the message to the [`ListRefresher`]
     #[tracing::instrument(skip(self))]
-    pub fn mouse_input(&mut self, mouse_pos: (f64, f64), mult: f64) -> Result<()> {
+    pub fn mouse_input(&mut self, square: (u32, u32)) -> Result<()> {
         match std::mem::take(&mut self.last_pressed) {
             Coords::OffBoard => {
-                let lp_x = to_board_coord(mouse_pos.0, mult);
-                let lp_y = to_board_coord(mouse_pos.1, mult);
-
-                let coord = (lp_x, lp_y).try_into()?;
+                let coord = square.try_into()?;
 
                 if self.board.piece_exists_at_location(coord) {
                     self.last_pressed = coord;
@@ -75,22 +120,24 @@ impl ChessGame {
             }
             Coords::OnBoard(x, y) => {
                 //Deal with second press
-                let current_press = {
-                    let lp_x = to_board_coord(mouse_pos.0, mult);
-                    let lp_y = to_board_coord(mouse_pos.1, mult);
-                    (lp_x, lp_y)
-                };
+                let current_press = square;
+
+                let m = JSONMove::new(
+                    self.id,
+                    u32::from(x),
+                    u32::from(y),
+                    current_press.0,
+                    current_press.1,
+                );
+                if let Err(e) = self.board.validate_move(m) {
+                    warn!(%e, "Not sending move");
+                    return Ok(());
+                }
 
                 info!(last_pos=?(x, y), new_pos=?current_press, "Starting moving");
 
                 self.refresher
-                    .send_msg(MessageToWorker::MakeMove(JSONMove::new(
-                        self.id,
-                        u32::from(x),
-                        u32::from(y),
-                        current_press.0,
-                        current_press.1,
-                    )))
+                    .send_msg(MessageToWorker::MakeMove(m))
                     .context("sending a message to the worker re moving")?;
 
                 self.ex_last_pressed = Coords::OnBoard(x, y);
@@ -100,6 +147,80 @@ impl ChessGame {
         Ok(())
     }
 
+    ///Gets the kinds of all of the pieces of one colour which have been taken, using [`captured_kinds`]
+    pub fn captured(&self, white: bool) -> Vec<ChessPieceKind> {
+        captured_kinds(&self.board.get_taken(), white)
+    }
+
+    ///Scores the board in centipawns from white's point of view, using the difference in material - positive is good for white
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn material_score(&self) -> i32 {
+        let (white, black) = self.board.count_material();
+        (white as i32 - black as i32) * 100
+    }
+
+    ///Gets the coordinates of the king to highlight because it is in check, or `None` if no king is in check
+    pub fn check_highlight(&self) -> Option<Coords> {
+        match &self.editor {
+            Some(editor) => editor.board().king_in_check(),
+            None => self.board.king_in_check(),
+        }
+    }
+
+    ///Gets the piece to draw at `coords` - from the [`SetupEditor`] if it is open, or from the game otherwise
+    fn shown_piece(&self, coords: Coords) -> Option<ChessPiece> {
+        match &self.editor {
+            Some(editor) => editor.board()[coords],
+            None => self.board[coords],
+        }
+    }
+
+    ///Checks whether or not the [`SetupEditor`] is open
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    ///Opens a new, empty [`SetupEditor`], or closes the open one and logs whether or not its position could come from a real game
+    pub fn toggle_editor(&mut self) {
+        self.changed = true;
+
+        if let Some(editor) = self.editor.take() {
+            let (board, validity) = editor.finish();
+            let position = board.to_ascii();
+            match validity {
+                Ok(()) => info!(%position, "Closed the position editor"),
+                Err(e) => warn!(%e, %position, "Closed the position editor with an impossible position"),
+            }
+        } else {
+            self.clear_mouse_input();
+            self.editor = Some(SetupEditor::default());
+        }
+    }
+
+    ///Handles a click while the [`SetupEditor`] is open - picks a piece if the click is on the palette, or places the picked piece if it is on the board
+    pub fn editor_click(&mut self, raw_mouse_pos: (f64, f64), window_scale: f64, is_flipped: bool) {
+        if let Some(editor) = &mut self.editor {
+            if let Some(piece) = palette_piece(raw_mouse_pos, window_scale) {
+                editor.pick(piece);
+            } else if let Some(square) = window_to_square(raw_mouse_pos, window_scale, is_flipped) {
+                match square.try_into() {
+                    Ok(coords) => editor.place(coords),
+                    Err(e) => warn!(%e, ?square, "Couldn't place a piece"),
+                }
+            }
+        }
+    }
+
+    ///Removes the piece at `square` from the [`SetupEditor`], if it is open
+    pub fn editor_clear(&mut self, square: (u32, u32)) {
+        if let Some(editor) = &mut self.editor {
+            match square.try_into() {
+                Ok(coords) => editor.clear(coords),
+                Err(e) => warn!(%e, ?square, "Couldn't clear a square"),
+            }
+        }
+    }
+
     ///Sends a message to the [`ListRefresher`] to clear the board for a new game.
     ///
     /// # Errors:
@@ -118,10 +239,20 @@ impl ChessGame {
     #[tracing::instrument(skip(self))]
     pub fn exit(self) -> Result<()> {
         self.refresher
-            .send_msg(MessageToWorker::InvalidateKill)
+            .shutdown()
             .context("sending invalidatekill msg to board")
     }
 
+    ///Checks whether or not the board has changed since this was last called, and resets it
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    ///Checks whether or not something is still being shown temporarily, so needs to keep being rendered
+    pub fn is_animating(&self) -> bool {
+        self.show_board_update.is_some()
+    }
+
     ///Clears the mouse input - means that a different piece can be selected.
     pub fn clear_mouse_input(&mut self) {
         self.last_pressed = Coords::OffBoard;
@@ -139,21 +270,35 @@ impl ChessGame {
         graphics: &mut G2d,
         raw_mouse_coords: (f64, f64),
         window_scale: f64,
-        is_flipped: bool
+        is_flipped: bool,
+        debug_overlay: bool,
     ) -> Result<()> {
-        let board_coords = if mp_valid(raw_mouse_coords, window_scale) {
-            let bps = to_board_pixels(raw_mouse_coords, window_scale);
-            Some((
-                to_board_coord(bps.0, window_scale),
-                to_board_coord(bps.1, window_scale),
-            ))
-        } else {
-            None
-        };
+        let board_coords = window_to_square(raw_mouse_coords, window_scale, is_flipped);
 
         clear([0.0; 4], graphics);
         let t = ctx.transform;
-        {
+        if let Some(theme) = &self.theme {
+            Rectangle::new(theme.border).draw(
+                square(0.0, 0.0, BOARD_S * window_scale),
+                &DrawState::default(),
+                t,
+                graphics,
+            );
+
+            let squares_trans = t.trans(LEFT_BOUND * window_scale, LEFT_BOUND * window_scale);
+            for col in 0..8 {
+                for row in 0..8 {
+                    //coloured by the logical square, so a1 stays dark when flipped
+                    let (x, y) = tile_origin(col, row, window_scale, is_flipped);
+                    Rectangle::new(square_colour(col, row, theme)).draw(
+                        square(x, y, BOARD_TILE_S * window_scale),
+                        &DrawState::default(),
+                        squares_trans,
+                        graphics,
+                    );
+                }
+            }
+        } else {
             let image = Image::new().rect(square(0.0, 0.0, BOARD_S * window_scale));
             let tex = self
                 .cache
@@ -170,12 +315,7 @@ impl ChessGame {
 
         {
             if let Some((px, py)) = board_coords {
-                let x = f64::from(px) * BOARD_TILE_S * window_scale;
-                let y = if is_flipped {
-                    f64::from(7 - py)
-                } else {
-                    f64::from(py)
-                } * BOARD_TILE_S * window_scale;
+                let (x, y) = tile_origin(px, py, window_scale, is_flipped);
                 let image = Image::new().rect(square(x, y, TILE_S * window_scale));
 
                 image.draw(
@@ -189,11 +329,40 @@ impl ChessGame {
                 );
             }
         }
+
+        if let Some(Coords::OnBoard(kx, ky)) = self.check_highlight() {
+            ///Colour of the square under a king in check
+            const CHECK_COLOUR: [f32; 4] = [0.8, 0.0, 0.0, 0.6];
+
+            let (x, y) = tile_origin(u32::from(kx), u32::from(ky), window_scale, is_flipped);
+            Rectangle::new(CHECK_COLOUR).draw(
+                square(x, y, TILE_S * window_scale),
+                &DrawState::default(),
+                trans,
+                graphics,
+            );
+        }
+
+        if debug_overlay {
+            ///Colour of the debug grid lines
+            const GRID_COLOUR: [f32; 4] = [1.0, 0.0, 1.0, 0.8];
+
+            let grid_trans = t.trans(LEFT_BOUND * window_scale, LEFT_BOUND * window_scale);
+            let length = 8.0 * BOARD_TILE_S * window_scale;
+            let line = Line::new(GRID_COLOUR, 0.5);
+
+            for i in 0..=8_u32 {
+                let pos = f64::from(i) * BOARD_TILE_S * window_scale;
+                line.draw([pos, 0.0, pos, length], &DrawState::default(), grid_trans, graphics);
+                line.draw([0.0, pos, length, pos], &DrawState::default(), grid_trans, graphics);
+            }
+        }
+
         let mut errs = vec![];
 
         for col in 0..8_u8 {
             for row in 0..8_u8 {
-                if let Some(piece) = self.board[(col, row).into()] {
+                if let Some(piece) = self.shown_piece((col, row).into()) {
                     match self.cache.get(&piece.to_file_name()) {
                         Err(e) => {
                             errs.push(e.context(format!(
@@ -202,12 +371,12 @@ impl ChessGame {
                             )));
                         }
                         Ok(tex) => {
-                            let x = f64::from(col) * BOARD_TILE_S * window_scale;
-                            let y = if is_flipped {
-                                f64::from(7 - row)
-                            } else {
-                                f64::from(row)
-                            } * BOARD_TILE_S * window_scale;
+                            let (x, y) = tile_origin(
+                                u32::from(col),
+                                u32::from(row),
+                                window_scale,
+                                is_flipped,
+                            );
                             let image = Image::new().rect(square(x, y, TILE_S * window_scale));
 
                             let mut draw =
@@ -230,75 +399,113 @@ impl ChessGame {
         }
 
         {
-            ///Size in pixels for pieces which have been taken
-            const TAKEN_TILE_SIZE: f64 = TILE_S * 0.75;
-            ///Starting Y for Taken tiles, such that when all pieces are taken, it it centred
-            const START_Y: f64 = (BOARD_S - (TAKEN_TILE_SIZE * 16.0)) / 2.0; //16 pieces
-
-            let mut pieces = self.board.get_taken();
-            pieces.sort();
-
             let white_trans = t.trans(TAKEN_TILE_SIZE * window_scale, START_Y * window_scale);
             let black_trans = t.trans(
                 (RIGHT_BOUND + TAKEN_TILE_SIZE) * window_scale,
                 START_Y * window_scale,
             );
 
-            let mut white_dy = 0.0;
-            let mut black_dy = 0.0;
+            for (is_white, trans) in [(true, white_trans), (false, black_trans)] {
+                let mut dy = 0.0;
 
-            for p in pieces {
-                match self.cache.get(&p.to_file_name()) {
-                    Err(e) => errs
-                        .push(e.context(format!("cacher doesn't contain: {:?}", p.to_file_name()))),
-                    Ok(tex) => {
-                        if p.is_white {
-                            let img = Image::new().rect(square(
-                                0.0,
-                                white_dy * window_scale,
-                                TAKEN_TILE_SIZE * window_scale,
-                            ));
-                            white_dy += TAKEN_TILE_SIZE;
-                            img.draw(tex, &DrawState::default(), white_trans, graphics);
-                        } else {
+                //the palette takes the place of the taken pieces while editing
+                let kinds = if self.is_editing() {
+                    PALETTE.to_vec()
+                } else {
+                    self.captured(is_white)
+                };
+
+                for kind in kinds {
+                    let file_name = ChessPiece { kind, is_white }.to_file_name();
+                    match self.cache.get(&file_name) {
+                        Err(e) => {
+                            errs.push(e.context(format!("cacher doesn't contain: {file_name:?}")));
+                        }
+                        Ok(tex) => {
                             let img = Image::new().rect(square(
                                 0.0,
-                                black_dy * window_scale,
+                                dy * window_scale,
                                 TAKEN_TILE_SIZE * window_scale,
                             ));
-                            black_dy += TAKEN_TILE_SIZE;
-                            img.draw(tex, &DrawState::default(), black_trans, graphics);
+                            dy += TAKEN_TILE_SIZE;
+                            img.draw(tex, &DrawState::default(), trans, graphics);
                         }
                     }
                 }
             }
         }
 
-        {
-            let (raw_x, raw_y) = if is_flipped {
-                (raw_mouse_coords.0, BOARD_S * window_scale - raw_mouse_coords.1)
+        if !self.is_editing() {
+            ///Distance in pixels from the left edge of the window to the advantage bar
+            const BAR_X: f64 = 4.0;
+            ///Width in pixels of the advantage bar
+            const BAR_W: f64 = 6.0;
+            ///Colour of white's part of the advantage bar
+            const WHITE_COLOUR: [f32; 4] = [0.9, 0.9, 0.9, 1.0];
+            ///Colour of black's part of the advantage bar
+            const BLACK_COLOUR: [f32; 4] = [0.1, 0.1, 0.1, 1.0];
+
+            let length = RIGHT_BOUND - LEFT_BOUND;
+            let white_length = length * f64::from(eval_to_bar_fraction(self.material_score()));
+            //white fills up from whichever side white is playing from
+            let white_y = if is_flipped {
+                LEFT_BOUND
             } else {
-                raw_mouse_coords
+                RIGHT_BOUND - white_length
             };
-            if self.last_pressed.is_on_board() {
-                if let Some(piece) = self.board[self.last_pressed] {
-                    match self.cache.get(&piece.to_file_name()) {
-                        Ok(tex) => {
-                            let s = TILE_S * window_scale / 1.5;
-                            let image =
-                                Image::new().rect(square(raw_x - s / 2.0, raw_y - s / 2.0, s));
-                            image.draw(tex, &DrawState::default(), t, graphics);
-                        }
-                        Err(e) => {
-                            errs.push(e.context(format!(
-                                "Cacher doesn't contain: {} at ({:?} floating)",
-                                piece.to_file_name(),
-                                self.last_pressed
-                            )));
-                        }
+
+            Rectangle::new(BLACK_COLOUR).draw(
+                [
+                    BAR_X * window_scale,
+                    LEFT_BOUND * window_scale,
+                    BAR_W * window_scale,
+                    length * window_scale,
+                ],
+                &DrawState::default(),
+                t,
+                graphics,
+            );
+            Rectangle::new(WHITE_COLOUR).draw(
+                [
+                    BAR_X * window_scale,
+                    white_y * window_scale,
+                    BAR_W * window_scale,
+                    white_length * window_scale,
+                ],
+                &DrawState::default(),
+                t,
+                graphics,
+            );
+        }
+
+        {
+            let (raw_x, raw_y) = raw_mouse_coords;
+            let floating = match &self.editor {
+                Some(editor) => editor.brush(),
+                None if self.last_pressed.is_on_board() => {
+                    let piece = self.board[self.last_pressed];
+                    if piece.is_none() {
+                        self.last_pressed = Coords::OffBoard;
+                    }
+                    piece
+                }
+                None => None,
+            };
+
+            if let Some(piece) = floating {
+                match self.cache.get(&piece.to_file_name()) {
+                    Ok(tex) => {
+                        let s = TILE_S * window_scale / 1.5;
+                        let image =
+                            Image::new().rect(square(raw_x - s / 2.0, raw_y - s / 2.0, s));
+                        image.draw(tex, &DrawState::default(), t, graphics);
+                    }
+                    Err(e) => {
+                        errs.push(e.context(format!(
+                            "Cacher doesn't contain: {} (floating)",
+                            piece.to_file_name()
+                        )));
                     }
-                } else {
-                    self.last_pressed = Coords::OffBoard;
                 }
             }
         }
@@ -350,9 +557,11 @@ impl ChessGame {
     #[allow(irrefutable_let_patterns)]
     pub fn update_list(&mut self, ignore_timer: bool) -> Result<()> {
         let mut updated = false;
+        let mut changed = false;
         match self.refresher.try_recv() {
-            Ok(msg) => match msg {
-                MessageToGame::UpdateBoard(msg) => match msg {
+            Ok(MessageToGame::UpdateBoard(msg)) => {
+                changed = changes_screen(&msg);
+                match msg {
                     BoardMessage::TmpMove(m) => {
                         if let Either::Left(bo) = self.board.clone() {
                             self.board = Either::Right(bo.make_move(m));
@@ -372,20 +581,33 @@ impl ChessGame {
                                     self.board = Either::Left(bo.undo_move());
                                 }
                             }
+                        } else if matches!(outcome, MoveOutcome::CouldntProcessMove) {
+                            //the move never got as far as a TmpMove, so there is nothing to undo
+                            warn!("Move couldn't be sent to the server");
                         } else {
                             bail!("need move to update with outcome: {outcome:?}");
                         }
                     }
                     BoardMessage::NoConnectionList => {
+                        self.no_connection = true;
                         self.board = Either::Left(no_connection_list());
                     }
                     BoardMessage::NewList(l) => {
-                        updated = true;
-                        self.board = Either::Left(Board::new_json(l)?);
+                        self.no_connection = false;
+                        let new = Board::new_json(l)?;
+                        if let Err(e) = new.validate_position() {
+                            warn!(%e, "Server sent an impossible position");
+                        }
+                        //only show the update message if something other than our own move changed
+                        updated = match &self.board {
+                            Either::Left(bo) => !bo.diff(&new).is_empty(),
+                            Either::Right(bo) => !bo.diff(&new).is_empty(),
+                        };
+                        self.board = Either::Left(new);
                     },
                     BoardMessage::UseExisting => {}
-                },
-            },
+                }
+            }
             Err(e) => {
                 if e != TryRecvError::Empty {
                     error!(%e, "Try recv error from worker");
@@ -394,6 +616,7 @@ impl ChessGame {
             }
         }
 
+        self.changed |= changed;
         if updated {
             self.show_board_update = Some(DoOnInterval::new(Duration::from_millis(1_500)));
         }
@@ -408,8 +631,266 @@ impl ChessGame {
     }
 }
 
+///Checks whether or not a [`BoardMessage`] from the [`ListRefresher`] can change what is on the screen, so the next frame needs rendering
+///
+/// Only [`BoardMessage::UseExisting`] can't - anything else might move pieces or change the title
+pub fn changes_screen(msg: &BoardMessage) -> bool {
+    match msg {
+        BoardMessage::TmpMove(_)
+        | BoardMessage::Move(_)
+        | BoardMessage::NoConnectionList
+        | BoardMessage::NewList(_) => true,
+        BoardMessage::UseExisting => false,
+    }
+}
+
+///Gets the kinds of the pieces of one colour in `taken`, sorted by [`ChessPieceKind::value`] and then by kind so that equal values always come out in the same order
+pub fn captured_kinds(taken: &[ChessPiece], white: bool) -> Vec<ChessPieceKind> {
+    let mut kinds: Vec<ChessPieceKind> = taken
+        .iter()
+        .filter(|p| p.is_white == white)
+        .map(|p| p.kind)
+        .collect();
+    kinds.sort_by_key(|k| (k.value(), *k));
+    kinds
+}
+
+///Gets the top-left pixel of the tile at `(col, row)`, relative to the top-left tile, taking into account whether or not the board is flipped
+pub fn tile_origin(col: u32, row: u32, window_scale: f64, is_flipped: bool) -> (f64, f64) {
+    let row = if is_flipped { 7 - row } else { row };
+    (
+        f64::from(col) * BOARD_TILE_S * window_scale,
+        f64::from(row) * BOARD_TILE_S * window_scale,
+    )
+}
+
+///Gets the colour of the logical square at `(col, row)` from the [`BoardTheme`], ignoring any flipping - a8 at `(0, 0)` is light, and a1 is dark
+pub fn square_colour(col: u32, row: u32, theme: &BoardTheme) -> [f32; 4] {
+    if (col + row) % 2 == 0 {
+        theme.light
+    } else {
+        theme.dark
+    }
+}
+
+///How many centipawns of advantage fill about three quarters of the advantage bar - bigger makes the bar less sensitive
+pub const EVAL_BAR_SCALE: f32 = 400.0;
+
+///Maps a centipawn score (positive is good for white) to how much of the advantage bar should be white, from 0 to 1
+///
+/// Uses a logistic curve, so an even position is half-filled and the bar never quite fills up
+#[allow(clippy::cast_precision_loss)]
+pub fn eval_to_bar_fraction(score: i32) -> f32 {
+    1.0 / (1.0 + (-(score as f32) / EVAL_BAR_SCALE).exp())
+}
+
+///Gets the piece in the [`SetupEditor`] palette under a position on the window, or `None` if there isn't one
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn palette_piece(raw_mouse_pos: (f64, f64), window_scale: f64) -> Option<ChessPiece> {
+    let (x, y) = (raw_mouse_pos.0 / window_scale, raw_mouse_pos.1 / window_scale);
+
+    let is_white = if (TAKEN_TILE_SIZE..TAKEN_TILE_SIZE * 2.0).contains(&x) {
+        true
+    } else if (RIGHT_BOUND + TAKEN_TILE_SIZE..RIGHT_BOUND + TAKEN_TILE_SIZE * 2.0).contains(&x) {
+        false
+    } else {
+        return None;
+    };
+
+    if y < START_Y {
+        return None;
+    }
+    let index = ((y - START_Y) / TAKEN_TILE_SIZE).floor() as usize;
+
+    PALETTE
+        .get(index)
+        .map(|&kind| ChessPiece { kind, is_white })
+}
+
 ///Converts a pixel to a board coordinate, assuming that the mouse cursor is on the board
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 pub fn to_board_coord(p: f64, mult: f64) -> u32 {
     (p / (BOARD_TILE_S * mult)).floor() as u32
 }
+
+#[cfg(test)]
+///Tests for the pure layout functions
+mod tests {
+    use super::{
+        captured_kinds, changes_screen, eval_to_bar_fraction, palette_piece, square_colour,
+        tile_origin, EVAL_BAR_SCALE, PALETTE, START_Y, TAKEN_TILE_SIZE,
+    };
+    use crate::{
+        piston::BoardTheme,
+        pixel_size_consts::{BOARD_TILE_S, RIGHT_BOUND},
+    };
+    use async_chess_client::{
+        net::{
+            list_refresher::{BoardMessage, MoveOutcome},
+            server_interface::{JSONMove, JSONPieceList},
+        },
+        prelude::{ChessPiece, ChessPieceKind},
+    };
+
+    ///A theme with easy to tell apart colours
+    const THEME: BoardTheme = BoardTheme {
+        light: [1.0; 4],
+        dark: [0.0; 4],
+        border: [0.5; 4],
+    };
+
+    ///Squares alternate, with a8 and h1 light, and a1 and h8 dark
+    #[test]
+    fn square_colours_alternate() {
+        assert_eq!(square_colour(0, 0, &THEME), THEME.light);
+        assert_eq!(square_colour(7, 7, &THEME), THEME.light);
+        assert_eq!(square_colour(0, 7, &THEME), THEME.dark);
+        assert_eq!(square_colour(7, 0, &THEME), THEME.dark);
+
+        for col in 0..8 {
+            for row in 0..7 {
+                assert_ne!(
+                    square_colour(col, row, &THEME),
+                    square_colour(col, row + 1, &THEME)
+                );
+            }
+        }
+    }
+
+    ///Flipping moves a1 to the top-left corner, but it stays dark
+    #[test]
+    fn flipped_a1_is_top_left_and_dark() {
+        assert_eq!(tile_origin(0, 7, 1.0, true), (0.0, 0.0));
+        assert_eq!(square_colour(0, 7, &THEME), THEME.dark);
+    }
+
+    ///Tiles are one padded tile apart in both orientations, flipping only mirrors the rows, and everything scales with the window
+    #[test]
+    fn tile_origins_in_both_orientations() {
+        for scale in [1.0, 2.0, 0.5] {
+            let step = BOARD_TILE_S * scale;
+
+            assert_eq!(tile_origin(0, 0, scale, false), (0.0, 0.0));
+            assert_eq!(tile_origin(7, 7, scale, false), (7.0 * step, 7.0 * step));
+            assert_eq!(tile_origin(0, 0, scale, true), (0.0, 7.0 * step));
+            assert_eq!(tile_origin(7, 7, scale, true), (7.0 * step, 0.0));
+
+            for col in 0..8 {
+                for row in 0..8 {
+                    let (x, y) = (f64::from(col) * step, f64::from(row) * step);
+                    let flipped_y = f64::from(7 - row) * step;
+                    assert_eq!(tile_origin(col, row, scale, false), (x, y));
+                    assert_eq!(tile_origin(col, row, scale, true), (x, flipped_y));
+                }
+            }
+        }
+    }
+
+    ///Each row of both palette columns gives its piece at any scale, and anywhere else gives nothing
+    #[test]
+    fn palette_rows_and_columns() {
+        let white_x = TAKEN_TILE_SIZE * 1.5;
+        let black_x = RIGHT_BOUND + TAKEN_TILE_SIZE * 1.5;
+        let row_y = |row: f64| START_Y + TAKEN_TILE_SIZE * (row + 0.5);
+
+        for scale in [1.0, 2.0, 0.5] {
+            for (row, kind) in (0_u8..).zip(PALETTE) {
+                let y = row_y(f64::from(row)) * scale;
+                assert_eq!(
+                    palette_piece((white_x * scale, y), scale),
+                    Some(ChessPiece {
+                        kind,
+                        is_white: true
+                    })
+                );
+                assert_eq!(
+                    palette_piece((black_x * scale, y), scale),
+                    Some(ChessPiece {
+                        kind,
+                        is_white: false
+                    })
+                );
+            }
+
+            let outside = [
+                (white_x, START_Y / 2.0),
+                (white_x, row_y(6.0)),
+                (black_x, row_y(6.0)),
+                (TAKEN_TILE_SIZE / 2.0, row_y(0.0)),
+                (TAKEN_TILE_SIZE * 2.5, row_y(0.0)),
+                (RIGHT_BOUND + TAKEN_TILE_SIZE / 2.0, row_y(0.0)),
+                (RIGHT_BOUND + TAKEN_TILE_SIZE * 2.5, row_y(0.0)),
+            ];
+            for (x, y) in outside {
+                assert_eq!(
+                    palette_piece((x * scale, y * scale), scale),
+                    None,
+                    "({x}, {y})"
+                );
+            }
+        }
+    }
+
+    ///An even position fills half the bar, and big advantages get close to filling or emptying it without ever quite doing so
+    #[test]
+    fn eval_bar_fractions() {
+        assert!((eval_to_bar_fraction(0) - 0.5).abs() < f32::EPSILON);
+
+        let winning = eval_to_bar_fraction(3_000);
+        let losing = eval_to_bar_fraction(-3_000);
+        assert!(winning > 0.99 && winning <= 1.0, "{winning}");
+        assert!(losing < 0.01 && losing >= 0.0, "{losing}");
+
+        //a scale's worth of advantage is about three quarters of the bar
+        #[allow(clippy::cast_possible_truncation)]
+        let scale = EVAL_BAR_SCALE as i32;
+        assert!((eval_to_bar_fraction(scale) - 0.73).abs() < 0.01);
+        assert!((eval_to_bar_fraction(scale) + eval_to_bar_fraction(-scale) - 1.0).abs() < 1e-6);
+
+        let mut last = 0.0;
+        for score in (-2_000..=2_000).step_by(100) {
+            let fraction = eval_to_bar_fraction(score);
+            assert!(fraction > last, "bar shrank at {score}");
+            last = fraction;
+        }
+    }
+
+    ///Only one colour's pieces are listed, cheapest first, with knights before bishops
+    #[test]
+    fn captured_kinds_ordering() {
+        use ChessPieceKind::{Bishop, Knight, Pawn, Queen, Rook};
+
+        let taken: Vec<ChessPiece> = [
+            (Queen, true),
+            (Pawn, true),
+            (Bishop, true),
+            (Rook, false),
+            (Knight, true),
+            (Pawn, false),
+            (Pawn, true),
+            (Rook, true),
+        ]
+        .into_iter()
+        .map(|(kind, is_white)| ChessPiece { kind, is_white })
+        .collect();
+
+        assert_eq!(
+            captured_kinds(&taken, true),
+            vec![Pawn, Pawn, Knight, Bishop, Rook, Queen]
+        );
+        assert_eq!(captured_kinds(&taken, false), vec![Pawn, Rook]);
+        assert!(captured_kinds(&[], true).is_empty());
+    }
+
+    ///Moves and new lists need the screen redrawing, but being told to use the existing list doesn't
+    #[test]
+    fn board_messages_that_change_the_screen() {
+        let e2e4 = JSONMove::new(0, 4, 6, 4, 4);
+        assert!(changes_screen(&BoardMessage::TmpMove(e2e4)));
+        let empty_list = JSONPieceList::default();
+        assert!(changes_screen(&BoardMessage::NewList(empty_list)));
+        assert!(changes_screen(&BoardMessage::Move(MoveOutcome::Invalid)));
+        assert!(changes_screen(&BoardMessage::NoConnectionList));
+        assert!(!changes_screen(&BoardMessage::UseExisting));
+    }
+}
@@ -0,0 +1,145 @@
+use piston_window::{Button, Key, MouseButton};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+///Everything that can be done using a key or mouse button
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    ///Selects the piece under the mouse, or moves the selected piece there. In the editor, picks or places a piece instead
+    Select,
+    ///Deselects the selected piece. In the editor, clears the square under the mouse instead
+    ClearSelection,
+    ///Asks the server to restart the board
+    Restart,
+    ///Flips the board to be seen from black's side
+    Flip,
+    ///Toggles drawing the debug grid over the board
+    ToggleDebug,
+    ///Opens or closes the position editor
+    ToggleEditor,
+}
+
+///A button to do an [`Action`] with, as stored in the [`crate::piston::PistonConfig`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    ///The key or mouse button
+    pub button: Button,
+    ///What the button does
+    pub action: Action,
+}
+
+///Gets the buttons that each [`Action`] uses if it isn't rebound
+pub fn default_bindings() -> HashMap<Button, Action> {
+    HashMap::from([
+        (Button::Mouse(MouseButton::Left), Action::Select),
+        (Button::Mouse(MouseButton::Right), Action::ClearSelection),
+        (Button::Keyboard(Key::C), Action::Restart),
+        (Button::Keyboard(Key::F), Action::Flip),
+        (Button::Keyboard(Key::D), Action::ToggleDebug),
+        (Button::Keyboard(Key::E), Action::ToggleEditor),
+    ])
+}
+
+///Builds the full map of buttons to [`Action`]s from the [`default_bindings`] and the user's `rebinds`
+///
+/// Any [`Action`] which is in `rebinds` loses all of its default buttons, so rebinding is a replacement rather than an addition.
+///
+/// Buttons which end up with more than one action are returned in the second part of the tuple so they can be warned about:
+/// - If `rebinds` gives a button to two different actions, the first one in `rebinds` is kept. Giving a button the same action twice is fine
+/// - If a rebind takes a button that another action still has as a default, the rebind wins
+pub fn build_bindings(rebinds: &[KeyBinding]) -> (HashMap<Button, Action>, Vec<Button>) {
+    let mut bindings = default_bindings();
+    bindings.retain(|_, action| !rebinds.iter().any(|kb| kb.action == *action));
+
+    let mut conflicts = vec![];
+    let mut rebound = HashMap::with_capacity(rebinds.len());
+    for KeyBinding { button, action } in rebinds.iter().copied() {
+        match rebound.get(&button) {
+            Some(&existing) if existing != action => conflicts.push(button),
+            Some(_) => {}
+            None => {
+                rebound.insert(button, action);
+            }
+        }
+    }
+
+    //every action left in the defaults is different from the rebound ones, so anything replaced is a conflict
+    for (button, action) in rebound {
+        if bindings.insert(button, action).is_some() {
+            conflicts.push(button);
+        }
+    }
+
+    (bindings, conflicts)
+}
+
+///Builds the bindings using [`build_bindings`], and logs a warning for every conflict found
+pub fn load_bindings(rebinds: &[KeyBinding]) -> HashMap<Button, Action> {
+    let (bindings, conflicts) = build_bindings(rebinds);
+    for button in conflicts {
+        warn!(?button, "Button is bound to more than one action in the config");
+    }
+    bindings
+}
+
+#[cfg(test)]
+///Tests for merging the rebinds with the [`default_bindings`]
+mod tests {
+    use super::{build_bindings, default_bindings, Action, KeyBinding};
+    use piston_window::{Button, Key};
+
+    ///Shorthand for a [`KeyBinding`] on a keyboard key
+    fn rebind(key: Key, action: Action) -> KeyBinding {
+        KeyBinding {
+            button: Button::Keyboard(key),
+            action,
+        }
+    }
+
+    ///Rebinding flip to space moves it off F, and leaves everything else alone
+    #[test]
+    fn rebinding_replaces_the_default() {
+        let (bindings, conflicts) = build_bindings(&[rebind(Key::Space, Action::Flip)]);
+
+        let mut expected = default_bindings();
+        expected.remove(&Button::Keyboard(Key::F));
+        expected.insert(Button::Keyboard(Key::Space), Action::Flip);
+
+        assert_eq!(bindings, expected);
+        assert!(conflicts.is_empty());
+    }
+
+    ///Binding one key to two actions is reported, and the first binding is kept
+    #[test]
+    fn duplicate_binding_is_reported() {
+        let (bindings, conflicts) = build_bindings(&[
+            rebind(Key::Space, Action::Flip),
+            rebind(Key::Space, Action::Restart),
+        ]);
+
+        assert_eq!(conflicts, vec![Button::Keyboard(Key::Space)]);
+        assert_eq!(bindings.get(&Button::Keyboard(Key::Space)), Some(&Action::Flip));
+        assert!(!bindings.values().any(|&action| action == Action::Restart));
+    }
+
+    ///Binding one key to the same action twice isn't a conflict
+    #[test]
+    fn repeated_binding_isnt_a_conflict() {
+        let (bindings, conflicts) = build_bindings(&[
+            rebind(Key::Space, Action::Flip),
+            rebind(Key::Space, Action::Flip),
+        ]);
+
+        assert!(conflicts.is_empty());
+        assert_eq!(bindings.get(&Button::Keyboard(Key::Space)), Some(&Action::Flip));
+    }
+
+    ///Taking another action's default key is reported, and the rebind wins
+    #[test]
+    fn taking_a_default_is_reported() {
+        let (bindings, conflicts) = build_bindings(&[rebind(Key::D, Action::Flip)]);
+
+        assert_eq!(conflicts, vec![Button::Keyboard(Key::D)]);
+        assert_eq!(bindings.get(&Button::Keyboard(Key::D)), Some(&Action::Flip));
+    }
+}
@@ -19,23 +19,25 @@
 
 use crate::{egui_launcher::egui_main, piston::piston_main};
 use anyhow::{Context, Result};
-use async_chess_client::{prelude::ErrorExt, util::error_ext::ToAnyhowNotErr};
+use async_chess_client::{
+    logging::init_tracing, prelude::ErrorExt, util::error_ext::ToAnyhowNotErr,
+};
 use directories::ProjectDirs;
 use piston::PistonConfig;
 use serde_json::from_str;
-use std::{
-    env::{args, set_var, var},
-    fs::read_to_string,
-};
-use tracing_subscriber::{
-    prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter, Registry,
-};
-use tracing_tree::HierarchicalLayer;
+use std::{env::args, fs::read_to_string};
+use tracing::Level;
 
+///Module to hold the list of assets the game needs
+mod assets;
+///Module to hold the [`editor::SetupEditor`] for composing positions
+mod editor;
 ///Module to deal with configurator
 mod egui_launcher;
 ///Module to hold the [`game::ChessGame`] struct and deal with its logic
 mod game;
+///Module to map keys and mouse buttons to actions
+mod keybindings;
 ///Module to hold windowing/rendering logic for the [`game::ChessGame`]
 mod piston;
 ///Module to hold useful constants for pixel sizes
@@ -67,7 +69,7 @@ extern crate tracing;
 extern crate anyhow;
 
 fn main() {
-    setup_logging_tracing().eprint_exit();
+    init_tracing(Level::INFO).eprint_exit();
 
     info!("Thanks to Devil's Workshop for the Chess Assets!");
 
@@ -97,14 +99,10 @@ fn start() {
     };
     info!(%user_wants_conf, ?uc);
 
-    if let Some(uc) = uc {
-        if !user_wants_conf {
-            piston_main(uc);
-            return;
-        }
+    match uc {
+        Some(uc) if !user_wants_conf => piston_main(uc),
+        uc => egui_main(uc),
     }
-
-    egui_main(uc);
 }
 
 ///Function to read in the config
@@ -131,34 +129,3 @@ pub fn read_config() -> Result<PistonConfig> {
         read_to_string(&conf_path).with_context(|| format!("reading path {conf_path:?}"))?;
     from_str::<PistonConfig>(&cntnts).with_context(|| format!("reading contents {cntnts}"))
 }
-
-///Function to setup all of the logging and tracing for the program
-///
-/// - Firstly, it sets the environment variables `RUST_LIB_BACKTRACE` to `1` and `RUST_LOG` to `info`
-/// - Then it sets up an Environment tracing logger with Tracing Tree
-///
-/// # Errors
-/// Can return an error if the tracing [`Registry`] fails to initialise, and this happens when:
-/// > `This method returns an error if a global default subscriber has already been set, or if a log logger has already been set (when the "tracing-log" feature is enabled).`
-#[tracing::instrument]
-pub fn setup_logging_tracing() -> Result<()> {
-    for (k, v) in &[("RUST_LIB_BACKTRACE", "1"), ("RUST_LOG", "info")] {
-        if var(k).is_err() {
-            println!("Setting {k} to {v}");
-            set_var(k, v);
-        }
-    }
-
-    Registry::default()
-        .with(EnvFilter::builder().from_env()?)
-        .with(
-            HierarchicalLayer::new(1)
-                .with_targets(true)
-                .with_bracketed_fields(true)
-                .with_verbose_entry(true)
-                .with_ansi(true), // .with_filter(Level::INFO.into())
-        )
-        .try_init()?;
-
-    Ok(())
-}
@@ -1,5 +1,7 @@
 use crate::{
-    game::ChessGame,
+    assets::{load_icon, verify_assets},
+    game::{to_board_coord, ChessGame},
+    keybindings::{load_bindings, Action, KeyBinding},
     pixel_size_consts::{BOARD_S, LEFT_BOUND, RIGHT_BOUND},
 };
 use anyhow::Context;
@@ -7,23 +9,94 @@ use async_chess_client::{
     prelude::ErrorExt, util::time_based_structs::memcache::MemoryTimedCacher,
 };
 use piston_window::{
-    Button, Key, MouseButton, MouseCursorEvent, PistonWindow, PressEvent, RenderEvent, UpdateEvent,
-    Window, WindowSettings,
+    AdvancedWindow, Button, EventLoop, FocusEvent, GenericEvent, MouseCursorEvent, PistonWindow,
+    PressEvent, RenderEvent, ResizeEvent, UpdateEvent, Window, WindowSettings,
 };
 use serde::{Deserialize, Serialize};
 
 ///Configuration for the Piston window
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PistonConfig {
     ///The game id
     pub id: u32,
     ///The width/height of the window
     pub res: u32,
+    ///The colours to draw the board with - if `None`, the board image is used instead
+    #[serde(default)]
+    pub theme: Option<BoardTheme>,
+    ///The most frames to render a second - if `None`, piston's default is used
+    #[serde(default)]
+    pub max_fps: Option<u64>,
+    ///Buttons to use instead of the defaults from [`crate::keybindings::default_bindings`]
+    #[serde(default)]
+    pub keybindings: Vec<KeyBinding>,
+}
+
+///Colours to draw the board with, instead of using the board image
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct BoardTheme {
+    ///RGBA colour of the light squares, including the top-left square
+    pub light: [f32; 4],
+    ///RGBA colour of the dark squares
+    pub dark: [f32; 4],
+    ///RGBA colour of everything around the squares
+    pub border: [f32; 4],
+}
+
+///Keeps track of whether or not anything on screen has changed, so frames can be skipped when idle
+///
+/// The window doesn't swap buffers by itself (see [`piston_main`]) - each frame that gets drawn is swapped in by hand, so skipped frames leave the last drawn frame on screen
+#[derive(Debug, Copy, Clone)]
+pub struct Dirty {
+    ///Whether or not the next frame needs rendering
+    dirty: bool,
+}
+
+impl Dirty {
+    ///Marks that something changed, and so the next frame needs rendering
+    pub fn mark(&mut self) {
+        self.dirty = true;
+    }
+
+    ///Marks that something changed if `e` is an input which can change what is drawn - a button press, the cursor moving, or the window being resized or focused
+    pub fn mark_on_input(&mut self, e: &impl GenericEvent) {
+        if e.press_args().is_some()
+            || e.mouse_cursor_args().is_some()
+            || e.resize_args().is_some()
+            || e.focus_args().is_some()
+        {
+            self.mark();
+        }
+    }
+
+    ///Checks whether or not the next frame needs rendering
+    pub fn is_dirty(self) -> bool {
+        self.dirty
+    }
+
+    ///Records that a frame was just rendered
+    pub fn rendered(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Default for Dirty {
+    ///Starts off dirty, so that the first frame gets rendered
+    fn default() -> Self {
+        Self { dirty: true }
+    }
 }
 
 ///Starts up a piston window using the given [`PistonConfig`]
 #[tracing::instrument(skip(pc))]
 pub fn piston_main(pc: PistonConfig) {
+    if let Err(problems) = verify_assets() {
+        for problem in problems {
+            error!(%problem, "Problem with assets");
+        }
+        std::process::exit(1);
+    }
+
     let mut win: PistonWindow = WindowSettings::new("Async Chess", [pc.res, pc.res])
         .exit_on_esc(true)
         .resizable(true)
@@ -31,9 +104,18 @@ pub fn piston_main(pc: PistonConfig) {
         .map_err(|e| anyhow!("{e}"))
         .context("making window")
         .unwrap_log_error();
+    match load_icon() {
+        Ok(icon) => win.window.ctx.window().set_window_icon(Some(icon)),
+        Err(e) => warn!(?e, "Unable to set the window icon"),
+    }
     // win.set_ups(5);
+    //buffers are only swapped after a frame is actually drawn, so that skipped frames keep showing the last one
+    win.set_swap_buffers(false);
+    if let Some(fps) = pc.max_fps {
+        win.set_max_fps(fps);
+    }
 
-    let mut game = ChessGame::new(&mut win, pc.id)
+    let mut game = ChessGame::new(&mut win, pc.id, pc.theme)
         .context("new chess game")
         .unwrap_log_error();
 
@@ -43,57 +125,86 @@ pub fn piston_main(pc: PistonConfig) {
     let mut time_since_last_frame = 0.0;
     let mut cached_dt = MemoryTimedCacher::<_, 100>::default();
     let mut is_flipped = false;
+    let mut debug_overlay = false;
+    let mut title = String::new();
+    let mut dirty = Dirty::default();
+    let bindings = load_bindings(&pc.keybindings);
 
     while let Some(e) = win.next() {
-        let window_scale = win.size().height / BOARD_S;
+        let window_scale = scale_for_board_size(win.size().height);
+
+        let new_title = game.title();
+        if new_title != title {
+            win.set_title(new_title.clone());
+            title = new_title;
+        }
 
         if time_since_last_frame == 0.0 || cached_dt.is_empty() {
             debug!(fps=%(1.0 / time_since_last_frame), cached_fps=%(1.0 / cached_dt.average_f64()));
         }
 
+        if game.take_changed() || game.is_animating() {
+            dirty.mark();
+        }
+
         if let Some(r) = e.render_args() {
             time_since_last_frame = r.ext_dt;
             cached_dt.add(r.ext_dt);
 
-            win.draw_2d(&e, |c, g, _device| {
-                game.render(c, g, mouse_pos, window_scale, is_flipped)
-                    .context("rendering")
-                    .error();
-            });
+            if dirty.is_dirty() {
+                win.draw_2d(&e, |c, g, _device| {
+                    game.render(c, g, mouse_pos, window_scale, is_flipped, debug_overlay)
+                        .context("rendering")
+                        .error();
+                });
+                win.swap_buffers();
+                dirty.rendered();
+            }
         }
 
         if let Some(_u) = e.update_args() {
             game.update_list(false).context("on update args").error();
         }
 
+        dirty.mark_on_input(&e);
+
         if let Some(pa) = e.press_args() {
             let mut update_now = false;
 
-            match pa {
-                Button::Keyboard(kb) => {
-                    info!(?kb, "Keyboard Input");
-
-                    match kb {
-                        Key::C => {
-                            //Clear
-                            game.restart_board().context("restart on c key").error();
-                            update_now = true;
-                        },
-                        Key::F =>  is_flipped = !is_flipped,
-                        _ => {}
-                    }
+            if let Button::Keyboard(kb) = pa {
+                info!(?kb, "Keyboard Input");
+            }
+
+            match bindings.get(&pa) {
+                Some(Action::Restart) => {
+                    game.restart_board().context("restart on key").error();
+                    update_now = true;
                 }
-                Button::Mouse(mb) => {
-                    if mb == MouseButton::Right {
+                Some(Action::Flip) => is_flipped = !is_flipped,
+                Some(Action::ToggleDebug) => debug_overlay = !debug_overlay,
+                Some(Action::ToggleEditor) => game.toggle_editor(),
+                Some(Action::ClearSelection) => {
+                    if !game.is_editing() {
                         game.clear_mouse_input();
-                    } else if mp_valid(mouse_pos, window_scale) {
-                        game.mouse_input(to_board_pixels(mouse_pos, window_scale), window_scale)
+                    } else if let Some(square) =
+                        window_to_square(mouse_pos, window_scale, is_flipped)
+                    {
+                        game.editor_clear(square);
+                    }
+                }
+                Some(Action::Select) => {
+                    if game.is_editing() {
+                        game.editor_click(mouse_pos, window_scale, is_flipped);
+                    } else if let Some(square) =
+                        window_to_square(mouse_pos, window_scale, is_flipped)
+                    {
+                        game.mouse_input(square)
                             .context("dealing with mouse input")
                             .error();
                         update_now = true;
                     }
                 }
-                _ => {}
+                None => {}
             }
 
             game.update_list(update_now)
@@ -102,11 +213,7 @@ pub fn piston_main(pc: PistonConfig) {
         }
 
         e.mouse_cursor(|p| {
-            if is_flipped {
-                mouse_pos = (p[0], (BOARD_S * window_scale) - p[1]);
-            } else {
-                mouse_pos = (p[0], p[1]);
-            }
+            mouse_pos = (p[0], p[1]);
         });
     }
 
@@ -114,6 +221,27 @@ pub fn piston_main(pc: PistonConfig) {
     game.exit().context("clearing up").error();
 }
 
+///Builds the title for the window from the state of the game
+///
+/// - `offline` is whether or not the board is showing the no connection list
+/// - `waiting_for_server` is whether or not a move has been made and the server hasn't replied yet
+pub fn window_title(id: u32, offline: bool, waiting_for_server: bool) -> String {
+    let mut title = format!("Async Chess - Game {id}");
+    if offline {
+        title.push_str(" (no connection)");
+    } else if waiting_for_server {
+        title.push_str(" (waiting for server)");
+    }
+    title
+}
+
+///Gets the scale to pass to [`ChessGame::render`] to draw the whole board at `board_size` pixels wide
+///
+/// Every size in [`crate::pixel_size_consts`] gets multiplied by this, so a tile is drawn at `TILE_S * scale` pixels
+pub fn scale_for_board_size(board_size: f64) -> f64 {
+    board_size / BOARD_S
+}
+
 ///Checks whether or not the mouse is on the board
 ///
 /// Must always be called BEFORE [`to_board_pixels`]
@@ -133,3 +261,127 @@ pub fn to_board_pixels(raw_mouse_pos: (f64, f64), window_scale: f64) -> (f64, f6
         raw_mouse_pos.1 - LEFT_BOUND * window_scale,
     )
 }
+
+///Converts a position on the window to the logical board square under it, as `(x, y)`, taking into account whether or not the board is flipped
+///
+/// Returns `None` if the position isn't on the board
+pub fn window_to_square(
+    raw_mouse_pos: (f64, f64),
+    window_scale: f64,
+    is_flipped: bool,
+) -> Option<(u32, u32)> {
+    let mouse_pos = if is_flipped {
+        (raw_mouse_pos.0, BOARD_S * window_scale - raw_mouse_pos.1)
+    } else {
+        raw_mouse_pos
+    };
+
+    if mp_valid(mouse_pos, window_scale) {
+        let (x, y) = to_board_pixels(mouse_pos, window_scale);
+        Some((to_board_coord(x, window_scale), to_board_coord(y, window_scale)))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+///Tests for the [`Dirty`] tracker and the window layout
+mod tests {
+    use super::{scale_for_board_size, window_title, window_to_square, Dirty};
+    use crate::pixel_size_consts::{BOARD_S, BOARD_TILE_S, LEFT_BOUND, TILE_S};
+    use piston_window::{
+        Button, ButtonArgs, ButtonState, Event, IdleArgs, Input, Key, Loop, Motion,
+    };
+
+    ///A frame with no input after a render doesn't need rendering
+    #[test]
+    fn frame_with_no_changes_is_clean() {
+        let mut dirty = Dirty::default();
+        assert!(dirty.is_dirty());
+        dirty.rendered();
+
+        dirty.mark_on_input(&Event::Loop(Loop::Idle(IdleArgs { dt: 0.1 })));
+        assert!(!dirty.is_dirty());
+    }
+
+    ///Key presses and cursor moves mark the next frame as needing rendering
+    #[test]
+    fn input_marks_dirty() {
+        let mut dirty = Dirty::default();
+        dirty.rendered();
+
+        let press = Input::Button(ButtonArgs {
+            state: ButtonState::Press,
+            button: Button::Keyboard(Key::F),
+            scancode: None,
+        });
+        dirty.mark_on_input(&Event::Input(press, None));
+        assert!(dirty.is_dirty());
+
+        dirty.rendered();
+        let cursor = Input::Move(Motion::MouseCursor([10.0, 20.0]));
+        dirty.mark_on_input(&Event::Input(cursor, None));
+        assert!(dirty.is_dirty());
+    }
+
+    ///The middle of the top-left tile is a8 normally and a1 when flipped, and anything outside the board isn't a square
+    #[test]
+    fn window_to_square_corners() {
+        let middle_of_first_tile = LEFT_BOUND + BOARD_TILE_S / 2.0;
+        for scale in [1.0, 2.0, 0.5] {
+            let top_left = (middle_of_first_tile * scale, middle_of_first_tile * scale);
+            assert_eq!(window_to_square(top_left, scale, false), Some((0, 0)));
+            assert_eq!(window_to_square(top_left, scale, true), Some((0, 7)));
+
+            let bottom_right = (
+                (BOARD_S - middle_of_first_tile) * scale,
+                (BOARD_S - middle_of_first_tile) * scale,
+            );
+            assert_eq!(window_to_square(bottom_right, scale, false), Some((7, 7)));
+            assert_eq!(window_to_square(bottom_right, scale, true), Some((7, 0)));
+
+            for off_board in [
+                (0.0, 0.0),
+                (LEFT_BOUND * scale / 2.0, top_left.1),
+                (top_left.0, BOARD_S * scale),
+            ] {
+                assert_eq!(
+                    window_to_square(off_board, scale, false),
+                    None,
+                    "{off_board:?}"
+                );
+                assert_eq!(
+                    window_to_square(off_board, scale, true),
+                    None,
+                    "{off_board:?}"
+                );
+            }
+        }
+    }
+
+    ///Drawing the board at half its size draws each tile at half its size, and the full size needs no scaling
+    #[test]
+    fn scale_follows_board_size() {
+        assert!((scale_for_board_size(BOARD_S / 2.0) * TILE_S - TILE_S / 2.0).abs() < f64::EPSILON);
+        assert!((scale_for_board_size(BOARD_S) - 1.0).abs() < f64::EPSILON);
+        assert!((scale_for_board_size(BOARD_S * 3.0) * BOARD_S - BOARD_S * 3.0).abs() < 1e-9);
+    }
+
+    ///The title names the game, and shows being offline over waiting for the server
+    #[test]
+    fn window_titles() {
+        assert_eq!(window_title(7, false, false), "Async Chess - Game 7");
+        assert_eq!(
+            window_title(7, true, false),
+            "Async Chess - Game 7 (no connection)"
+        );
+        assert_eq!(
+            window_title(7, false, true),
+            "Async Chess - Game 7 (waiting for server)"
+        );
+        assert_eq!(
+            window_title(7, true, true),
+            "Async Chess - Game 7 (no connection)"
+        );
+    }
+}
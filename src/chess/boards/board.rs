@@ -1,4 +1,5 @@
 use std::{
+    fmt::{Display, Formatter},
     marker::PhantomData,
     ops::{Index, IndexMut},
 };
@@ -8,11 +9,105 @@ use epac_utils::generic_enum;
 use crate::{
     crate_private::Sealed,
     net::server_interface::{JSONMove, JSONPieceList},
-    prelude::{ChessPiece, ChessPieceKind, Coords, Result},
+    prelude::{ChessPiece, ChessPieceKind, Coords, Direction, Result, SError},
 };
 
 generic_enum!(Sealed, (BoardMoveState -> "Holds the current state of moving pieces in the board to ensure no logic errors") => (CanMovePiece -> "The board can currently move a new piece"), (NeedsMoveUpdate -> "The board now needs an update on what happened to the piece it moved"));
 
+///Enum to hold the reasons a move can be rejected before it gets sent to the server
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    ///There is no piece to move at the starting square - holds the index of that square
+    EmptySource(usize),
+    ///The move has coordinates which aren't on the board
+    OutOfBounds,
+    ///The move could never be legal - the piece doesn't move, or it would take a piece of the same colour
+    Illegal(JSONMove),
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::EmptySource(index) => write!(f, "there is no piece to move at index {index}"),
+            MoveError::OutOfBounds => write!(f, "that move goes off the board"),
+            MoveError::Illegal(m) => write!(f, "that move can never be legal: {m:?}"),
+        }
+    }
+}
+
+impl SError for MoveError {}
+
+///Enum to hold the reasons a position can't come from a real game
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PositionError {
+    ///One side doesn't have exactly one king - holds whether that side is white, and how many kings it has
+    WrongKingCount(bool, usize),
+    ///There is a pawn on the first or last row, where it could never be
+    PawnOnBackRank(Coords),
+    ///Both kings are in check, so whoever is to move, the other side must have been left in check
+    BothKingsInCheck,
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PositionError::WrongKingCount(white, count) => write!(
+                f,
+                "{} has {count} kings instead of 1",
+                if *white { "white" } else { "black" }
+            ),
+            PositionError::PawnOnBackRank(coords) => {
+                write!(f, "there is a pawn on the back rank at {coords:?}")
+            }
+            PositionError::BothKingsInCheck => write!(f, "both kings are in check"),
+        }
+    }
+}
+
+impl SError for PositionError {}
+
+///Struct to hold the coordinates of the pawns of one colour with notable structure, from [`Board::pawn_structure`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PawnStructure {
+    ///Pawns which share a file with another pawn of the same colour
+    pub doubled: Vec<Coords>,
+    ///Pawns with no pawns of the same colour on either neighbouring file
+    pub isolated: Vec<Coords>,
+    ///Pawns with no enemy pawns in front of them on the same or neighbouring files
+    pub passed: Vec<Coords>,
+}
+
+///Enum to hold how far through a game a position is, judged by how much material is left - see [`Board::game_phase`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GamePhase {
+    ///At least [`OPENING_MATERIAL`] of non-pawn material is left
+    Opening,
+    ///Between the opening and endgame thresholds
+    Middlegame,
+    ///At most [`ENDGAME_MATERIAL`] of non-pawn material is left
+    Endgame,
+}
+
+///The least non-pawn material, for both sides together, for a position to be the [`GamePhase::Opening`] - the full 62 minus up to two minor pieces
+pub const OPENING_MATERIAL: u32 = 56;
+///The most non-pawn material, for both sides together, for a position to be the [`GamePhase::Endgame`] - around a rook and two minor pieces each
+pub const ENDGAME_MATERIAL: u32 = 26;
+
+///Formats a bitboard as an 8x8 grid for debugging, with an `X` for set bits and a `.` for unset bits
+///
+/// Bit `y * 8 + x` is the square at [`Coords::OnBoard`]`(x, y)`, the same as [`Coords::to_usize`], so a1 ends up at the bottom-left
+#[must_use]
+pub fn format_bitboard(bb: u64) -> String {
+    let mut s = String::with_capacity(8 * 9);
+    for y in 0..8 {
+        for x in 0..8 {
+            s.push(if bb & (1 << (y * 8 + x)) == 0 { '.' } else { 'X' });
+        }
+        s.push('\n');
+    }
+    s
+}
+
 ///Struct to hold a Chess Board
 #[derive(Clone, Debug)]
 pub struct Board<STATE: BoardMoveState> {
@@ -94,11 +189,350 @@ impl<STATE: BoardMoveState> Board<STATE> {
         }
     }
 
+    ///Checks a move for mistakes that don't need the server to catch, so the user can get feedback straight away
+    ///
+    /// # Errors
+    /// - [`MoveError::OutOfBounds`] if either set of coordinates is off the board
+    /// - [`MoveError::EmptySource`] if there is no piece at the starting coordinates
+    /// - [`MoveError::Illegal`] if the piece wouldn't move, or would land on a piece of the same colour
+    pub fn validate_move(&self, m: JSONMove) -> Result<(), MoveError> {
+        let current = Coords::try_from((m.x, m.y)).map_err(|_| MoveError::OutOfBounds)?;
+        let new = Coords::try_from((m.nx, m.ny)).map_err(|_| MoveError::OutOfBounds)?;
+
+        let piece = match self[current] {
+            Some(p) => p,
+            None => {
+                return Err(MoveError::EmptySource(
+                    current.to_usize().ae().context("current index").unwrap_log_error(),
+                ))
+            }
+        };
+
+        if current == new {
+            return Err(MoveError::Illegal(m));
+        }
+        if let Some(target) = self[new] {
+            if target.is_white == piece.is_white {
+                return Err(MoveError::Illegal(m));
+            }
+        }
+
+        Ok(())
+    }
+
+    ///Renders the board as an ASCII grid, with files and ranks around the edge and a `.` for each empty square
+    ///
+    /// Pieces use [`ChessPiece::to_char`], and white is at the bottom
+    #[must_use]
+    pub fn to_ascii(&self) -> String {
+        ///The file letters drawn above and below the grid
+        const FILES: &str = "  a b c d e f g h\n";
+
+        let mut s = String::from(FILES);
+        for y in 0..8_u8 {
+            let rank = 8 - y;
+            s.push_str(&format!("{rank} "));
+            for x in 0..8_u8 {
+                s.push(self[(x, y).into()].map_or('.', ChessPiece::to_char));
+                s.push(' ');
+            }
+            s.push_str(&format!("{rank}\n"));
+        }
+        s.push_str(FILES);
+
+        s
+    }
+
+    ///Gets the piece on the square with the given algebraic name, like `e4`
+    ///
+    /// Returns `None` if there is no piece there, or if the name isn't a valid square
+    #[must_use]
+    pub fn piece_at_name(&self, name: &str) -> Option<ChessPiece> {
+        Coords::try_from(name).ok().and_then(|c| self[c])
+    }
+
+    ///Checks that the position could happen in a real game
+    ///
+    /// # Errors
+    /// - [`PositionError::WrongKingCount`] if either side doesn't have exactly one king
+    /// - [`PositionError::PawnOnBackRank`] if there is a pawn on the top or bottom row
+    /// - [`PositionError::BothKingsInCheck`] if both kings are being attacked
+    pub fn validate_position(&self) -> Result<(), PositionError> {
+        for white in [true, false] {
+            let count = self
+                .pieces
+                .iter()
+                .filter(|p| {
+                    matches!(p, Some(ChessPiece { kind: ChessPieceKind::King, is_white }) if *is_white == white)
+                })
+                .count();
+            if count != 1 {
+                return Err(PositionError::WrongKingCount(white, count));
+            }
+        }
+
+        for y in [0, 7] {
+            for x in 0..8 {
+                let coords = Coords::OnBoard(x, y);
+                if matches!(self[coords], Some(p) if p.kind == ChessPieceKind::Pawn) {
+                    return Err(PositionError::PawnOnBackRank(coords));
+                }
+            }
+        }
+
+        if self.is_in_check(true) && self.is_in_check(false) {
+            return Err(PositionError::BothKingsInCheck);
+        }
+
+        Ok(())
+    }
+
     ///Gets a clone of all the pieces which have been taken
     #[must_use]
     pub fn get_taken(&self) -> Vec<ChessPiece> {
         self.taken.clone()
     }
+
+    ///Checks whether or not this board has the same pieces in the same places as `other`
+    ///
+    /// Ignores the taken pieces and any move waiting on the server. Placement is the only position state the server sends, so it is all that two boards can be compared on
+    #[must_use]
+    pub fn same_position<OTHER: BoardMoveState>(&self, other: &Board<OTHER>) -> bool {
+        self.pieces == other.pieces
+    }
+
+    ///Lists every square where this board and `other` differ, as `(coords, piece on this board, piece on other)`
+    ///
+    /// Squares are listed row by row, starting from the top-left
+    #[must_use]
+    pub fn diff<OTHER: BoardMoveState>(
+        &self,
+        other: &Board<OTHER>,
+    ) -> Vec<(Coords, Option<ChessPiece>, Option<ChessPiece>)> {
+        let mut changes = vec![];
+        for y in 0..8_u8 {
+            for x in 0..8_u8 {
+                let coords = (x, y).into();
+                let (old, new) = (self[coords], other[coords]);
+                if old != new {
+                    changes.push((coords, old, new));
+                }
+            }
+        }
+        changes
+    }
+
+    ///Checks whether or not any piece of the given colour attacks the square at `sq`, stopping at the first attacker found
+    ///
+    /// Only looks at where the pieces are, so the square doesn't need to be empty and it doesn't matter whose turn it is
+    #[must_use]
+    pub fn is_square_attacked(&self, sq: Coords, by_white: bool) -> bool {
+        !self.find_attackers(sq, by_white, true).is_empty()
+    }
+
+    ///Finds the coordinates of the king of the given colour, if it is on the board
+    #[must_use]
+    pub fn find_king(&self, white: bool) -> Option<Coords> {
+        self.pieces
+            .iter()
+            .position(|p| {
+                matches!(p, Some(ChessPiece { kind: ChessPieceKind::King, is_white }) if *is_white == white)
+            })
+            .and_then(|index| u32::try_from(index).ok())
+            .and_then(|index| Coords::try_from((index % 8, index / 8)).ok())
+    }
+
+    ///Checks whether or not the king of the given colour is being attacked
+    #[must_use]
+    pub fn is_in_check(&self, white: bool) -> bool {
+        self.find_king(white)
+            .map_or(false, |king| self.is_square_attacked(king, !white))
+    }
+
+    ///Gets the coordinates of all of the enemy pieces attacking the king of the given colour
+    ///
+    /// Empty if the king isn't in check or isn't on the board
+    #[must_use]
+    pub fn checkers(&self, white: bool) -> Vec<Coords> {
+        self.find_king(white)
+            .map(|king| self.find_attackers(king, !white, false))
+            .unwrap_or_default()
+    }
+
+    ///Gets the coordinates of the pieces of the given colour which are attacked by the other colour, and not defended by their own
+    ///
+    /// Kings are never counted as they can't be taken
+    #[must_use]
+    pub fn hanging_pieces(&self, white: bool) -> Vec<Coords> {
+        let mut hanging = vec![];
+        for y in 0..8_u8 {
+            for x in 0..8_u8 {
+                let coords = (x, y).into();
+                if matches!(self[coords], Some(p) if p.is_white == white && p.kind != ChessPieceKind::King)
+                    && self.is_square_attacked(coords, !white)
+                    && !self.is_square_attacked(coords, white)
+                {
+                    hanging.push(coords);
+                }
+            }
+        }
+        hanging
+    }
+
+    ///Finds the doubled, isolated and passed pawns of the given colour - see [`PawnStructure`]
+    #[must_use]
+    pub fn pawn_structure(&self, white: bool) -> PawnStructure {
+        let mut own = vec![];
+        let mut enemy = vec![];
+        for y in 0..8_u8 {
+            for x in 0..8_u8 {
+                if let Some(p) = self[(x, y).into()] {
+                    if p.kind == ChessPieceKind::Pawn {
+                        if p.is_white == white {
+                            own.push((x, y));
+                        } else {
+                            enemy.push((x, y));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut structure = PawnStructure::default();
+        for &(x, y) in &own {
+            let coords = Coords::OnBoard(x, y);
+
+            if own.iter().any(|&(ox, oy)| ox == x && oy != y) {
+                structure.doubled.push(coords);
+            }
+            if !own.iter().any(|&(ox, _)| ox.abs_diff(x) == 1) {
+                structure.isolated.push(coords);
+            }
+
+            //white pawns move towards y = 0
+            let blocked = enemy.iter().any(|&(ex, ey)| {
+                ex.abs_diff(x) <= 1 && if white { ey < y } else { ey > y }
+            });
+            if !blocked {
+                structure.passed.push(coords);
+            }
+        }
+
+        structure
+    }
+
+    ///Works out the [`GamePhase`] from the total [`ChessPieceKind::value`] of the knights, bishops, rooks and queens on the board
+    #[must_use]
+    pub fn game_phase(&self) -> GamePhase {
+        let material: u32 = self
+            .pieces
+            .iter()
+            .flatten()
+            .filter(|p| p.kind != ChessPieceKind::Pawn)
+            .map(|p| p.kind.value())
+            .sum();
+
+        if material >= OPENING_MATERIAL {
+            GamePhase::Opening
+        } else if material <= ENDGAME_MATERIAL {
+            GamePhase::Endgame
+        } else {
+            GamePhase::Middlegame
+        }
+    }
+
+    ///Sums up the [`ChessPieceKind::value`] of each side's pieces, as `(white, black)`. Kings are worth 0, so never count
+    #[must_use]
+    pub fn count_material(&self) -> (u32, u32) {
+        self.pieces
+            .iter()
+            .flatten()
+            .fold((0, 0), |(white, black), p| {
+                if p.is_white {
+                    (white + p.kind.value(), black)
+                } else {
+                    (white, black + p.kind.value())
+                }
+            })
+    }
+
+    ///Gets the coordinates of whichever king is in check, or `None` if neither is
+    ///
+    /// White is checked first, so if both kings are in check (which [`Self::validate_position`] rejects) this gives white's king
+    #[must_use]
+    pub fn king_in_check(&self) -> Option<Coords> {
+        [true, false]
+            .into_iter()
+            .find(|white| self.is_in_check(*white))
+            .and_then(|white| self.find_king(white))
+    }
+
+    ///Finds the coordinates of the pieces of the given colour which attack the square at `sq`
+    ///
+    /// If `stop_at_first` is true, this returns as soon as one attacker is found
+    fn find_attackers(&self, sq: Coords, by_white: bool, stop_at_first: bool) -> Vec<Coords> {
+        let mut attackers = vec![];
+        if sq.is_taken() {
+            return attackers;
+        }
+
+        let mut check = |coords: Coords, kinds: &[ChessPieceKind]| {
+            if let Some(p) = self[coords] {
+                if p.is_white == by_white && kinds.contains(&p.kind) {
+                    attackers.push(coords);
+                }
+            }
+            stop_at_first && !attackers.is_empty()
+        };
+
+        //white pawns move north, so they attack from the south
+        let pawn_dirs = if by_white {
+            [Direction::SouthEast, Direction::SouthWest]
+        } else {
+            [Direction::NorthEast, Direction::NorthWest]
+        };
+        for dir in pawn_dirs {
+            if let Some(c) = sq.step(dir) {
+                if check(c, &[ChessPieceKind::Pawn]) {
+                    return attackers;
+                }
+            }
+        }
+
+        for (dirs, kind) in [
+            (Direction::knight_jumps(), ChessPieceKind::Knight),
+            (Direction::sliding(), ChessPieceKind::King),
+        ] {
+            for dir in dirs {
+                if let Some(c) = sq.step(dir) {
+                    if check(c, &[kind]) {
+                        return attackers;
+                    }
+                }
+            }
+        }
+
+        for dir in Direction::sliding() {
+            let slider = if dir.is_diagonal() {
+                ChessPieceKind::Bishop
+            } else {
+                ChessPieceKind::Rook
+            };
+
+            let mut current = sq.step(dir);
+            while let Some(c) = current {
+                if self[c].is_some() {
+                    if check(c, &[slider, ChessPieceKind::Queen]) {
+                        return attackers;
+                    }
+                    break;
+                }
+                current = c.step(dir);
+            }
+        }
+
+        attackers
+    }
 }
 
 impl Board<CanMovePiece> {
@@ -117,6 +551,28 @@ impl Board<CanMovePiece> {
         })
     }
 
+    ///Create a new board with only the given pieces on it. Any pieces at [`Coords::OffBoard`] count as taken
+    ///
+    /// # Errors
+    /// Returns an error if two pieces are placed at the same coordinates
+    pub fn from_pieces(pieces: &[(Coords, ChessPiece)]) -> Result<Self> {
+        let mut board = Self::default();
+        for &(coords, piece) in pieces {
+            if coords.is_taken() {
+                board.taken.push(piece);
+                continue;
+            }
+
+            let square = &mut board[coords];
+            if square.is_some() {
+                bail!("Collision at {coords:?}");
+            }
+            *square = Some(piece);
+        }
+
+        Ok(board)
+    }
+
     ///Makes a move using a given [`JSONMove`]
     ///
     /// - Firstly, finds the piece to be taken, and sets the cache to the details of that piece
@@ -158,6 +614,15 @@ impl Board<CanMovePiece> {
             _pd: PhantomData,
         }
     }
+
+    ///Makes a move on a clone of the board using [`Board::make_move`], leaving this board untouched
+    ///
+    /// # Errors
+    /// Returns the [`MoveError`] from [`Board::validate_move`] if the move can't be made
+    pub fn clone_with_move(&self, m: JSONMove) -> Result<Board<NeedsMoveUpdate>, MoveError> {
+        self.validate_move(m)?;
+        Ok(self.clone().make_move(m))
+    }
 }
 
 impl Board<NeedsMoveUpdate> {
@@ -212,3 +677,541 @@ impl Board<NeedsMoveUpdate> {
         }
     }
 }
+
+#[cfg(test)]
+///Tests for the position queries on [`Board`]
+mod tests {
+    use super::{
+        format_bitboard, Board, CanMovePiece, GamePhase, MoveError, PawnStructure, PositionError,
+        ENDGAME_MATERIAL, OPENING_MATERIAL,
+    };
+    use crate::net::server_interface::JSONMove;
+    use crate::prelude::{ChessPiece, ChessPieceKind, Coords};
+
+    ///Shorthand for a [`ChessPiece`]
+    fn piece(kind: ChessPieceKind, is_white: bool) -> ChessPiece {
+        ChessPiece { kind, is_white }
+    }
+
+    ///Builds a board from pieces placed by algebraic square name
+    fn board(pieces: &[(&str, ChessPieceKind, bool)]) -> Board<CanMovePiece> {
+        let pieces: Vec<_> = pieces
+            .iter()
+            .map(|&(name, kind, white)| (Coords::try_from(name).unwrap(), piece(kind, white)))
+            .collect();
+        Board::from_pieces(&pieces).unwrap()
+    }
+
+    ///Gets the coordinates of a square from its algebraic name
+    fn sq(name: &str) -> Coords {
+        Coords::try_from(name).unwrap()
+    }
+
+    ///Parses a UCI move for game 0
+    fn mv(uci: &str) -> JSONMove {
+        JSONMove::from_uci(0, uci).unwrap()
+    }
+
+    ///Builds the normal starting position
+    fn start_position() -> Board<CanMovePiece> {
+        use ChessPieceKind::{Bishop, King, Knight, Pawn, Queen, Rook};
+        ///The pieces on the back ranks, from the a-file to the h-file
+        const BACK_RANK: [ChessPieceKind; 8] =
+            [Rook, Knight, Bishop, Queen, King, Bishop, Knight, Rook];
+
+        let mut pieces = vec![];
+        for (x, kind) in (0_u8..).zip(BACK_RANK) {
+            pieces.push(((x, 0).into(), piece(kind, false)));
+            pieces.push(((x, 1).into(), piece(Pawn, false)));
+            pieces.push(((x, 6).into(), piece(Pawn, true)));
+            pieces.push(((x, 7).into(), piece(kind, true)));
+        }
+        Board::from_pieces(&pieces).unwrap()
+    }
+
+    ///A knight on the h-file never attacks anything on the a or b files
+    #[test]
+    fn h_file_knight_never_attacks_the_a_file() {
+        for rank in 1..=8 {
+            let from = format!("h{rank}");
+            let b = board(&[(&from, ChessPieceKind::Knight, true)]);
+            for target_rank in 1..=8 {
+                for file in ["a", "b"] {
+                    let target = sq(&format!("{file}{target_rank}"));
+                    assert!(
+                        !b.is_square_attacked(target, true),
+                        "knight on {from} attacks {target:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    ///White pawns attack towards the top of the board, and black pawns towards the bottom
+    #[test]
+    fn pawn_attack_directions() {
+        let b = board(&[("e4", ChessPieceKind::Pawn, true), ("e5", ChessPieceKind::Pawn, false)]);
+
+        assert!(b.is_square_attacked(sq("d5"), true));
+        assert!(b.is_square_attacked(sq("f5"), true));
+        assert!(!b.is_square_attacked(sq("d3"), true));
+        assert!(!b.is_square_attacked(sq("f3"), true));
+        assert!(!b.is_square_attacked(sq("e5"), true));
+
+        assert!(b.is_square_attacked(sq("d4"), false));
+        assert!(b.is_square_attacked(sq("f4"), false));
+        assert!(!b.is_square_attacked(sq("d6"), false));
+        assert!(!b.is_square_attacked(sq("f6"), false));
+        assert!(!b.is_square_attacked(sq("e4"), false));
+    }
+
+    ///Sliding pieces attack up to and including the first piece in the way, but not past it
+    #[test]
+    fn sliders_are_blocked() {
+        let b = board(&[
+            ("a1", ChessPieceKind::Rook, true),
+            ("a4", ChessPieceKind::Pawn, false),
+            ("c1", ChessPieceKind::Bishop, true),
+            ("e3", ChessPieceKind::Knight, true),
+            ("h8", ChessPieceKind::Queen, false),
+        ]);
+
+        assert!(b.is_square_attacked(sq("a3"), true));
+        assert!(b.is_square_attacked(sq("a4"), true));
+        assert!(!b.is_square_attacked(sq("a5"), true));
+
+        assert!(b.is_square_attacked(sq("d2"), true));
+        assert!(b.is_square_attacked(sq("e3"), true));
+        assert!(!b.is_square_attacked(sq("f4"), true));
+
+        assert!(b.is_square_attacked(sq("a1"), false));
+        assert!(b.is_square_attacked(sq("h1"), false));
+        assert!(!b.is_square_attacked(sq("g6"), false));
+    }
+
+    ///Kings and knights attack the squares next to them and their jumps, and nothing else
+    #[test]
+    fn kings_and_knights_attack_nearby() {
+        let b = board(&[("d4", ChessPieceKind::King, true), ("h8", ChessPieceKind::Knight, false)]);
+
+        assert!(b.is_square_attacked(sq("e5"), true));
+        assert!(b.is_square_attacked(sq("c3"), true));
+        assert!(!b.is_square_attacked(sq("d6"), true));
+
+        assert!(b.is_square_attacked(sq("g6"), false));
+        assert!(b.is_square_attacked(sq("f7"), false));
+        assert!(!b.is_square_attacked(sq("g7"), false));
+    }
+
+    ///A rook and a knight both giving check are both found
+    #[test]
+    fn double_check_has_two_checkers() {
+        let b = board(&[
+            ("e1", ChessPieceKind::King, true),
+            ("a8", ChessPieceKind::King, false),
+            ("e8", ChessPieceKind::Rook, false),
+            ("f3", ChessPieceKind::Knight, false),
+        ]);
+
+        let mut checkers = b.checkers(true);
+        checkers.sort_by_key(Coords::to_usize);
+        assert_eq!(checkers, vec![sq("e8"), sq("f3")]);
+        assert!(b.is_in_check(true));
+        assert!(b.checkers(false).is_empty());
+    }
+
+    ///A checker behind another piece doesn't count
+    #[test]
+    fn blocked_checker_is_ignored() {
+        let b = board(&[
+            ("e1", ChessPieceKind::King, true),
+            ("e2", ChessPieceKind::Bishop, true),
+            ("e8", ChessPieceKind::Rook, false),
+            ("b4", ChessPieceKind::Bishop, false),
+        ]);
+
+        assert_eq!(b.checkers(true), vec![sq("b4")]);
+    }
+
+    ///A lone attacked queen is hanging, while a defended rook isn't
+    #[test]
+    fn lone_queen_is_hanging() {
+        let b = board(&[
+            ("e1", ChessPieceKind::King, true),
+            ("d5", ChessPieceKind::Queen, true),
+            ("e8", ChessPieceKind::King, false),
+            ("d8", ChessPieceKind::Rook, false),
+        ]);
+
+        assert_eq!(b.hanging_pieces(true), vec![sq("d5")]);
+        assert!(b.hanging_pieces(false).is_empty());
+    }
+
+    ///Attacked kings and defended pieces are never hanging
+    #[test]
+    fn kings_and_defended_pieces_arent_hanging() {
+        let b = board(&[
+            ("e1", ChessPieceKind::King, true),
+            ("d5", ChessPieceKind::Queen, true),
+            ("d1", ChessPieceKind::Rook, true),
+            ("e8", ChessPieceKind::King, false),
+            ("d8", ChessPieceKind::Rook, false),
+            ("e5", ChessPieceKind::Rook, false),
+        ]);
+
+        assert!(b.hanging_pieces(true).is_empty());
+    }
+
+    ///Doubled c-pawns, an isolated passed h-pawn, and a black d-pawn holding back the rest
+    #[test]
+    fn doubled_isolated_and_passed_pawns() {
+        let b = board(&[
+            ("c2", ChessPieceKind::Pawn, true),
+            ("c3", ChessPieceKind::Pawn, true),
+            ("d2", ChessPieceKind::Pawn, true),
+            ("h3", ChessPieceKind::Pawn, true),
+            ("d7", ChessPieceKind::Pawn, false),
+        ]);
+
+        assert_eq!(
+            b.pawn_structure(true),
+            PawnStructure {
+                doubled: vec![sq("c3"), sq("c2")],
+                isolated: vec![sq("h3")],
+                passed: vec![sq("h3")],
+            }
+        );
+        assert_eq!(
+            b.pawn_structure(false),
+            PawnStructure {
+                doubled: vec![],
+                isolated: vec![sq("d7")],
+                passed: vec![],
+            }
+        );
+    }
+
+    ///The ASCII board has a file border, a row per rank with white at the bottom, then another file border
+    #[test]
+    fn ascii_board_layout() {
+        let b = board(&[
+            ("e1", ChessPieceKind::King, true),
+            ("e8", ChessPieceKind::King, false),
+            ("a2", ChessPieceKind::Pawn, true),
+        ]);
+        let ascii = b.to_ascii();
+        let lines: Vec<&str> = ascii.lines().collect();
+
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "  a b c d e f g h");
+        assert_eq!(lines[1], "8 . . . . k . . . 8");
+        assert_eq!(lines[7], "2 P . . . . . . . 2");
+        assert_eq!(lines[8], "1 . . . . K . . . 1");
+        assert_eq!(lines[9], lines[0]);
+    }
+
+    ///Each way a move can be rejected gives its own [`MoveError`], and a fine move gives `Ok`
+    #[test]
+    fn validate_move_errors() {
+        let b = board(&[
+            ("e2", ChessPieceKind::Pawn, true),
+            ("e3", ChessPieceKind::Knight, true),
+            ("d3", ChessPieceKind::Pawn, false),
+        ]);
+
+        assert_eq!(
+            b.validate_move(JSONMove::new(0, 8, 6, 4, 4)),
+            Err(MoveError::OutOfBounds)
+        );
+        assert_eq!(
+            b.validate_move(JSONMove::new(0, 4, 6, 4, 8)),
+            Err(MoveError::OutOfBounds)
+        );
+        //a2 is (0, 6), so index 6 * 8 + 0
+        assert_eq!(b.validate_move(mv("a2a3")), Err(MoveError::EmptySource(48)));
+        assert_eq!(
+            b.validate_move(mv("e2e2")),
+            Err(MoveError::Illegal(mv("e2e2")))
+        );
+        assert_eq!(
+            b.validate_move(mv("e2e3")),
+            Err(MoveError::Illegal(mv("e2e3")))
+        );
+
+        assert_eq!(b.validate_move(mv("e3e5")), Ok(()));
+        assert_eq!(b.validate_move(mv("e2d3")), Ok(()));
+    }
+
+    ///Each way a position can be impossible gives its own [`PositionError`], and a legal position gives `Ok`
+    #[test]
+    fn validate_position_errors() {
+        use ChessPieceKind::{King, Pawn, Rook};
+
+        assert_eq!(
+            board(&[("e8", King, false)]).validate_position(),
+            Err(PositionError::WrongKingCount(true, 0))
+        );
+        assert_eq!(
+            board(&[("e1", King, true)]).validate_position(),
+            Err(PositionError::WrongKingCount(false, 0))
+        );
+        assert_eq!(
+            board(&[("e1", King, true), ("a1", King, true), ("e8", King, false)])
+                .validate_position(),
+            Err(PositionError::WrongKingCount(true, 2))
+        );
+        assert_eq!(
+            board(&[("e1", King, true), ("e8", King, false), ("a8", King, false)])
+                .validate_position(),
+            Err(PositionError::WrongKingCount(false, 2))
+        );
+
+        assert_eq!(
+            board(&[("e1", King, true), ("e8", King, false), ("a8", Pawn, false)])
+                .validate_position(),
+            Err(PositionError::PawnOnBackRank(sq("a8")))
+        );
+        assert_eq!(
+            board(&[("e1", King, true), ("e8", King, false), ("h1", Pawn, true)])
+                .validate_position(),
+            Err(PositionError::PawnOnBackRank(sq("h1")))
+        );
+
+        assert_eq!(
+            board(&[
+                ("e1", King, true),
+                ("e8", King, false),
+                ("a8", Rook, true),
+                ("a1", Rook, false),
+            ])
+            .validate_position(),
+            Err(PositionError::BothKingsInCheck)
+        );
+
+        assert_eq!(
+            board(&[
+                ("e1", King, true),
+                ("e8", King, false),
+                ("e2", Pawn, true),
+                ("e7", Pawn, false),
+            ])
+            .validate_position(),
+            Ok(())
+        );
+    }
+
+    ///Each side starts with 39 points of material, and losing a piece only changes that side's total
+    #[test]
+    fn count_material_totals() {
+        let mut b = start_position();
+        assert_eq!(b.count_material(), (39, 39));
+
+        b[sq("d8")] = None;
+        assert_eq!(b.count_material(), (39, 30));
+        assert_eq!(Board::default().count_material(), (0, 0));
+    }
+
+    ///The start position is the opening, bare kings are the endgame, and the thresholds themselves belong to the opening and endgame
+    #[test]
+    fn game_phases() {
+        use ChessPieceKind::{King, Knight, Pawn, Queen, Rook};
+
+        let mut b = start_position();
+        assert_eq!(b.game_phase(), GamePhase::Opening);
+
+        //losing two knights leaves exactly the opening threshold
+        b[sq("b1")] = None;
+        b[sq("g8")] = None;
+        assert_eq!(OPENING_MATERIAL, 56);
+        assert_eq!(b.game_phase(), GamePhase::Opening);
+        b[sq("g1")] = None;
+        assert_eq!(b.game_phase(), GamePhase::Middlegame);
+
+        let kings = [("e1", King, true), ("e8", King, false)];
+        assert_eq!(board(&kings).game_phase(), GamePhase::Endgame);
+
+        //pawns don't count, so this is still the endgame
+        let pawns = board(&[kings[0], kings[1], ("a2", Pawn, true), ("a7", Pawn, false)]);
+        assert_eq!(pawns.game_phase(), GamePhase::Endgame);
+
+        //queen and rook against queen and knight is exactly the endgame threshold
+        let endgame = [
+            kings[0],
+            kings[1],
+            ("d1", Queen, true),
+            ("a1", Rook, true),
+            ("d8", Queen, false),
+            ("b8", Knight, false),
+        ];
+        assert_eq!(ENDGAME_MATERIAL, 26);
+        assert_eq!(board(&endgame).game_phase(), GamePhase::Endgame);
+
+        let mut middlegame = endgame.to_vec();
+        middlegame.push(("h8", Rook, false));
+        assert_eq!(board(&middlegame).game_phase(), GamePhase::Middlegame);
+    }
+
+    ///Boards with the same placement are the same position whatever was taken and whatever state they're in, and moving a piece makes them different
+    #[test]
+    fn same_position_only_compares_placement() {
+        let start = start_position();
+
+        let mut with_taken = start_position();
+        with_taken.taken.push(piece(ChessPieceKind::Queen, true));
+        assert!(start.same_position(&with_taken));
+        assert!(with_taken.same_position(&start));
+
+        let moved = start.clone().make_move(mv("e2e4"));
+        assert!(!start.same_position(&moved));
+        assert!(!moved.same_position(&start));
+
+        let mut by_hand = start_position();
+        by_hand[sq("e4")] = by_hand[sq("e2")].take();
+        assert!(by_hand.same_position(&moved));
+        assert!(moved.same_position(&by_hand));
+    }
+
+    ///A capture after one move shows up as exactly the two squares involved, with the pieces before and after
+    #[test]
+    fn diff_after_one_move() {
+        use ChessPieceKind::{King, Knight, Pawn};
+
+        let before = board(&[
+            ("e1", King, true),
+            ("e8", King, false),
+            ("f3", Knight, true),
+            ("e5", Pawn, false),
+        ]);
+        let after = before.clone().make_move(mv("f3e5"));
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                (
+                    sq("e5"),
+                    Some(piece(Pawn, false)),
+                    Some(piece(Knight, true))
+                ),
+                (sq("f3"), Some(piece(Knight, true)), None),
+            ]
+        );
+        assert!(before.diff(&before).is_empty());
+    }
+
+    ///Trying a move on a clone leaves the original board alone, and invalid moves give back the [`MoveError`]
+    #[test]
+    fn clone_with_move_leaves_the_source() {
+        let start = start_position();
+
+        let moved = start.clone_with_move(mv("e2e4")).unwrap();
+        assert!(start.same_position(&start_position()));
+        assert_eq!(
+            start.piece_at_name("e2"),
+            Some(piece(ChessPieceKind::Pawn, true))
+        );
+        assert_eq!(start.piece_at_name("e4"), None);
+        assert_eq!(
+            moved.piece_at_name("e4"),
+            Some(piece(ChessPieceKind::Pawn, true))
+        );
+
+        assert_eq!(
+            start.clone_with_move(mv("e4e5")).unwrap_err(),
+            MoveError::EmptySource(sq("e4").to_usize().unwrap())
+        );
+        assert_eq!(
+            start.clone_with_move(mv("e1d1")).unwrap_err(),
+            MoveError::Illegal(mv("e1d1"))
+        );
+        assert!(start.same_position(&start_position()));
+    }
+
+    ///Building a board from pieces matches placing them by hand, pieces off the board are taken, and two pieces can't share a square
+    #[test]
+    fn from_pieces_places_and_takes() {
+        let white_king = piece(ChessPieceKind::King, true);
+        let black_rook = piece(ChessPieceKind::Rook, false);
+        let white_knight = piece(ChessPieceKind::Knight, true);
+
+        let built = Board::from_pieces(&[
+            (sq("e1"), white_king),
+            (sq("a8"), black_rook),
+            (Coords::OffBoard, white_knight),
+        ])
+        .unwrap();
+
+        let mut by_hand = Board::<CanMovePiece>::default();
+        by_hand[sq("e1")] = Some(white_king);
+        by_hand[sq("a8")] = Some(black_rook);
+
+        assert!(built.same_position(&by_hand));
+        assert_eq!(built.get_taken(), vec![white_knight]);
+        assert!(by_hand.get_taken().is_empty());
+
+        let collision = Board::from_pieces(&[(sq("e1"), white_king), (sq("e1"), black_rook)]);
+        assert!(collision.unwrap_err().to_string().contains("Collision"));
+    }
+
+    ///Squares are looked up by name on the start position, and names which aren't squares give `None`
+    #[test]
+    fn piece_at_name_lookups() {
+        let start = start_position();
+
+        assert_eq!(
+            start.piece_at_name("e2"),
+            Some(piece(ChessPieceKind::Pawn, true))
+        );
+        assert_eq!(
+            start.piece_at_name("e8"),
+            Some(piece(ChessPieceKind::King, false))
+        );
+        assert_eq!(start.piece_at_name("e4"), None);
+
+        for name in ["i1", "e9", "", "e10"] {
+            assert_eq!(start.piece_at_name(name), None, "{name:?}");
+        }
+    }
+
+    ///The checked king is found while the check lasts, but not once a piece is in the way, and white's king comes first if both are in check
+    #[test]
+    fn king_in_check_until_blocked() {
+        use ChessPieceKind::{King, Pawn, Rook};
+
+        let mut b = board(&[("e1", King, true), ("a8", King, false), ("e8", Rook, false)]);
+        assert_eq!(b.king_in_check(), Some(sq("e1")));
+
+        b[sq("e2")] = Some(piece(Pawn, true));
+        assert_eq!(b.king_in_check(), None);
+
+        let both = board(&[
+            ("e1", King, true),
+            ("a8", King, false),
+            ("e8", Rook, false),
+            ("a1", Rook, true),
+        ]);
+        assert_eq!(both.king_in_check(), Some(sq("e1")));
+    }
+
+    ///Bits are drawn row by row from a8, so bit 56 (a1) is the first character of the last row
+    #[test]
+    fn bitboard_layout() {
+        let empty = "........\n".repeat(8);
+        assert_eq!(format_bitboard(0), empty);
+
+        let a1 = format_bitboard(1 << 56);
+        let rows: Vec<_> = a1.lines().collect();
+        assert_eq!(rows.len(), 8);
+        assert_eq!(rows[7], "X.......");
+        assert!(rows[..7].iter().all(|row| *row == "........"));
+        assert_eq!(a1.matches('X').count(), 1);
+        assert_eq!(sq("a1").to_usize(), Some(56));
+
+        //the first rank is the last row
+        let first_rank = format_bitboard(0xFF << 56);
+        assert_eq!(first_rank, format!("{}XXXXXXXX\n", "........\n".repeat(7)));
+
+        //and the eighth rank is the first
+        let eighth_rank = format_bitboard(0xFF);
+        assert_eq!(eighth_rank, format!("XXXXXXXX\n{}", "........\n".repeat(7)));
+    }
+}
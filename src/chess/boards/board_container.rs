@@ -1,7 +1,10 @@
 use std::ops::{Index, IndexMut};
 use epac_utils::either::Either;
-use crate::prelude::{ChessPiece, Coords};
-use super::board::{Board, CanMovePiece, NeedsMoveUpdate};
+use crate::{
+    net::server_interface::JSONMove,
+    prelude::{ChessPiece, Coords},
+};
+use super::board::{Board, CanMovePiece, MoveError, NeedsMoveUpdate};
 
 ///Struct to hold board states for utility purposes
 pub type BoardContainer = Either<Board<CanMovePiece>, Board<NeedsMoveUpdate>>;
@@ -26,23 +29,24 @@ macro_rules! method_on_original_ref {
         }
     };
 }
-///Macro for use with [`BoardContainer`] that just repeats board functions
-macro_rules! method_on_original_mut_ref {
-    ($func_name:ident $func_return:ty => $($arg_name:ident $arg_type:ty),*) => {
-        impl BoardContainer {
-            pub fn $func_name (&mut self, $($arg_name: $arg_type)*) -> $func_return {
-                match self {
-                    Self::Left(l) => l.$func_name($($arg_name,)*),
-                    Self::Right(l) => l.$func_name($($arg_name),*),
-                }
-            }
+method_on_original_ref!(piece_exists_at_location bool => coords Coords);
+method_on_original_ref!(get_taken Vec<ChessPiece> => );
+method_on_original_ref!(king_in_check Option<Coords> => );
+method_on_original_ref!(count_material (u32, u32) => );
+
+impl BoardContainer {
+    ///Checks a move against whichever board is held - see [`Board::validate_move`]
+    ///
+    /// # Errors
+    /// Returns the [`MoveError`] from [`Board::validate_move`]
+    pub fn validate_move(&self, m: JSONMove) -> Result<(), MoveError> {
+        match self {
+            Self::Left(b) => b.validate_move(m),
+            Self::Right(b) => b.validate_move(m),
         }
-    };
+    }
 }
 
-method_on_original_ref!(piece_exists_at_location bool => coords Coords);
-method_on_original_mut_ref!(get_taken Vec<ChessPiece> => );
-
 impl Index<Coords> for BoardContainer {
     type Output = Option<ChessPiece>;
 
@@ -21,6 +21,50 @@ pub enum ChessPieceKind {
     Rook = 3,
 }
 
+//the discriminants are part of any byte encoding of pieces, so fail to compile if they ever change
+const _: () = {
+    assert!(ChessPieceKind::Pawn as u8 == 0);
+    assert!(ChessPieceKind::Knight as u8 == 1);
+    assert!(ChessPieceKind::Bishop as u8 == 2);
+    assert!(ChessPieceKind::Rook as u8 == 3);
+    assert!(ChessPieceKind::Queen as u8 == 4);
+    assert!(ChessPieceKind::King as u8 == 5);
+};
+
+impl ChessPieceKind {
+    ///Gets all of the kinds that a pawn can be promoted to - everything except [`Self::Pawn`] and [`Self::King`]
+    #[must_use]
+    pub const fn promotion_candidates() -> [ChessPieceKind; 4] {
+        [Self::Queen, Self::Rook, Self::Bishop, Self::Knight]
+    }
+
+    ///Safely converts a byte into the kind with that discriminant, or `None` if there isn't one
+    #[must_use]
+    pub const fn from_u8(v: u8) -> Option<ChessPieceKind> {
+        match v {
+            0 => Some(Self::Pawn),
+            1 => Some(Self::Knight),
+            2 => Some(Self::Bishop),
+            3 => Some(Self::Rook),
+            4 => Some(Self::Queen),
+            5 => Some(Self::King),
+            _ => None,
+        }
+    }
+
+    ///Gets the material value of the piece kind in pawns. [`Self::King`] is worth 0 as it can never be taken
+    #[must_use]
+    pub const fn value(self) -> u32 {
+        match self {
+            Self::Pawn => 1,
+            Self::Knight | Self::Bishop => 3,
+            Self::Rook => 5,
+            Self::Queen => 9,
+            Self::King => 0,
+        }
+    }
+}
+
 ///Enum to hold errors for chess piece kinds
 #[derive(Debug, Display)]
 pub enum ChessPieceKindParseError {
@@ -75,6 +119,12 @@ impl ChessPiece {
         v
     }
 
+    ///Checks whether or not two pieces are the same kind, ignoring their colours
+    #[must_use]
+    pub fn same_kind(self, other: ChessPiece) -> bool {
+        self.kind == other.kind
+    }
+
     ///Converts a [`ChessPiece`] to a file name
     #[must_use]
     pub fn to_file_name(self) -> String {
@@ -84,6 +134,25 @@ impl ChessPiece {
             self.kind.to_string().to_lowercase()
         )
     }
+
+    ///Converts a [`ChessPiece`] to its letter - uppercase for white pieces and lowercase for black pieces
+    #[must_use]
+    pub fn to_char(self) -> char {
+        let c = match self.kind {
+            ChessPieceKind::Bishop => 'b',
+            ChessPieceKind::Knight => 'n',
+            ChessPieceKind::Pawn => 'p',
+            ChessPieceKind::Queen => 'q',
+            ChessPieceKind::King => 'k',
+            ChessPieceKind::Rook => 'r',
+        };
+
+        if self.is_white {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        }
+    }
 }
 
 impl Debug for ChessPiece {
@@ -109,3 +178,71 @@ impl Ord for ChessPiece {
             .unwrap_log_error_with_context(|| format!("comparing {self:?} to {other:?}"))
     }
 }
+
+#[cfg(test)]
+///Tests for converting and comparing pieces
+mod tests {
+    use super::{ChessPiece, ChessPieceKind};
+    use strum::IntoEnumIterator;
+
+    ///Every kind survives being converted to its discriminant and back with [`ChessPieceKind::from_u8`], and bytes past the last kind don't
+    #[test]
+    fn from_u8_round_trips() {
+        for kind in ChessPieceKind::iter() {
+            assert_eq!(ChessPieceKind::from_u8(kind as u8), Some(kind));
+        }
+
+        assert_eq!(ChessPieceKind::from_u8(6), None);
+        assert_eq!(ChessPieceKind::from_u8(255), None);
+    }
+
+    ///Locks the `#[repr(u8)]` discriminants that byte encodings rely on, checking them at runtime alongside the compile-time asserts
+    #[test]
+    fn discriminants_are_stable() {
+        use ChessPieceKind::{Bishop, King, Knight, Pawn, Queen, Rook};
+
+        for (byte, kind) in [Pawn, Knight, Bishop, Rook, Queen, King]
+            .into_iter()
+            .enumerate()
+        {
+            let byte = u8::try_from(byte).expect("only six kinds");
+            assert_eq!(kind as u8, byte);
+            assert_eq!(ChessPieceKind::from_u8(byte), Some(kind));
+        }
+
+        assert_eq!(ChessPieceKind::from_u8(6), None);
+    }
+
+    ///Pawns can be promoted to everything except pawns and kings
+    #[test]
+    fn promotion_candidates() {
+        use ChessPieceKind::{Bishop, King, Knight, Pawn, Queen, Rook};
+
+        let candidates = ChessPieceKind::promotion_candidates();
+        assert_eq!(candidates, [Queen, Rook, Bishop, Knight]);
+        assert!(!candidates.contains(&Pawn));
+        assert!(!candidates.contains(&King));
+    }
+
+    ///Pieces are the same kind whatever their colours, and different kinds never are
+    #[test]
+    fn same_kind_ignores_colour() {
+        let white_rook = ChessPiece {
+            kind: ChessPieceKind::Rook,
+            is_white: true,
+        };
+        let black_rook = ChessPiece {
+            is_white: false,
+            ..white_rook
+        };
+        assert!(white_rook.same_kind(black_rook));
+        assert!(black_rook.same_kind(white_rook));
+        assert!(white_rook.same_kind(white_rook));
+
+        for a in ChessPiece::all_variants() {
+            for b in ChessPiece::all_variants() {
+                assert_eq!(a.same_kind(b), a.kind == b.kind, "{a:?} and {b:?}");
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use strum::EnumIter;
 
 ///Utility type to hold a set of [`u8`] coordinates in an `(x, y)` format. Can also represent a piece which was taken.
 ///
@@ -66,6 +67,33 @@ impl TryFrom<(u32, u32)> for Coords {
     }
 }
 
+impl TryFrom<&str> for Coords {
+    type Error = anyhow::Error;
+
+    ///Parses an algebraic square name like `e4`, where `a8` is (0, 0) and `h1` is (7, 7)
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        let name = name.trim().to_lowercase();
+        let mut chars = name.chars();
+
+        let (file, rank) = match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => (file, rank),
+            _ => bail!("{name:?} isn't two characters long"),
+        };
+        if !('a'..='h').contains(&file) {
+            bail!("file {file:?} isn't between a and h")
+        }
+        if !('1'..='8').contains(&rank) {
+            bail!("rank {rank:?} isn't between 1 and 8")
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Self::OnBoard(
+            (u32::from(file) - u32::from('a')) as u8,
+            (u32::from('8') - u32::from(rank)) as u8,
+        )) //conversion works as all checked above
+    }
+}
+
 impl From<Coords> for Option<(u8, u8)> {
     fn from(c: Coords) -> Self {
         c.to_option()
@@ -106,6 +134,27 @@ impl Coords {
         }
     }
 
+    ///Provides the algebraic name of the square, like `e4`, or `None` if it is off the board
+    #[must_use]
+    pub fn to_name(&self) -> Option<String> {
+        self.to_option()
+            .map(|(x, y)| format!("{}{}", char::from(b'a' + x), 8 - y))
+    }
+
+    ///Gets the coordinates one step away in the given [`Direction`], or `None` if that is off the board or these coordinates already are
+    #[must_use]
+    pub fn step(&self, dir: Direction) -> Option<Coords> {
+        let (x, y) = self.to_option()?;
+        let (dx, dy) = dir.offset();
+        let (x, y) = (i32::from(x) + i32::from(dx), i32::from(y) + i32::from(dy));
+
+        if (0..8).contains(&x) && (0..8).contains(&y) {
+            Coords::try_from((x, y)).ok()
+        } else {
+            None
+        }
+    }
+
     ///Utility function for whether or not it is taken
     #[must_use]
     pub fn is_taken(&self) -> bool {
@@ -118,3 +167,276 @@ impl Coords {
         matches!(self, Coords::OnBoard(_, _))
     }
 }
+
+///Enum to hold all of the directions a piece can move in one step, including the knight jumps
+///
+/// North is towards y = 0, which is where white pawns move towards
+#[derive(EnumIter, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Direction {
+    ///Up one row
+    North,
+    ///Up one row and right one column
+    NorthEast,
+    ///Right one column
+    East,
+    ///Down one row and right one column
+    SouthEast,
+    ///Down one row
+    South,
+    ///Down one row and left one column
+    SouthWest,
+    ///Left one column
+    West,
+    ///Up one row and left one column
+    NorthWest,
+    ///Knight jump - up two rows and right one column
+    NorthNorthEast,
+    ///Knight jump - up one row and right two columns
+    EastNorthEast,
+    ///Knight jump - down one row and right two columns
+    EastSouthEast,
+    ///Knight jump - down two rows and right one column
+    SouthSouthEast,
+    ///Knight jump - down two rows and left one column
+    SouthSouthWest,
+    ///Knight jump - down one row and left two columns
+    WestSouthWest,
+    ///Knight jump - up one row and left two columns
+    WestNorthWest,
+    ///Knight jump - up two rows and left one column
+    NorthNorthWest,
+}
+
+impl Direction {
+    ///Gets the `(dx, dy)` that one step in this direction changes the coordinates by
+    #[must_use]
+    pub const fn offset(self) -> (i8, i8) {
+        match self {
+            Self::North => (0, -1),
+            Self::NorthEast => (1, -1),
+            Self::East => (1, 0),
+            Self::SouthEast => (1, 1),
+            Self::South => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (-1, -1),
+            Self::NorthNorthEast => (1, -2),
+            Self::EastNorthEast => (2, -1),
+            Self::EastSouthEast => (2, 1),
+            Self::SouthSouthEast => (1, 2),
+            Self::SouthSouthWest => (-1, 2),
+            Self::WestSouthWest => (-2, 1),
+            Self::WestNorthWest => (-2, -1),
+            Self::NorthNorthWest => (-1, -2),
+        }
+    }
+
+    ///Gets the eight directions that kings step in, and rooks, bishops and queens slide in
+    #[must_use]
+    pub const fn sliding() -> [Direction; 8] {
+        [
+            Self::North,
+            Self::NorthEast,
+            Self::East,
+            Self::SouthEast,
+            Self::South,
+            Self::SouthWest,
+            Self::West,
+            Self::NorthWest,
+        ]
+    }
+
+    ///Gets the eight jumps a knight can make
+    #[must_use]
+    pub const fn knight_jumps() -> [Direction; 8] {
+        [
+            Self::NorthNorthEast,
+            Self::EastNorthEast,
+            Self::EastSouthEast,
+            Self::SouthSouthEast,
+            Self::SouthSouthWest,
+            Self::WestSouthWest,
+            Self::WestNorthWest,
+            Self::NorthNorthWest,
+        ]
+    }
+
+    ///Checks whether or not this is one of the four diagonal [`Self::sliding`] directions
+    #[must_use]
+    pub const fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Self::NorthEast | Self::SouthEast | Self::SouthWest | Self::NorthWest
+        )
+    }
+
+    ///Checks whether or not one step in this direction from `from` stays on the board
+    #[must_use]
+    pub fn stays_on_board(self, from: Coords) -> bool {
+        from.step(self).is_some()
+    }
+}
+
+#[cfg(test)]
+///Tests for stepping [`Coords`] around the board
+mod tests {
+    use super::{Coords, Direction};
+
+    ///Gets every square a knight on `from` can jump to
+    fn knight_targets(from: Coords) -> Vec<Coords> {
+        Direction::knight_jumps()
+            .into_iter()
+            .filter_map(|dir| from.step(dir))
+            .collect()
+    }
+
+    ///No knight jump from any square lands more than two files or ranks away
+    #[test]
+    fn knight_jumps_never_wrap() {
+        for x in 0..8_u8 {
+            for y in 0..8_u8 {
+                for dir in Direction::knight_jumps() {
+                    if let Some(Coords::OnBoard(nx, ny)) = Coords::OnBoard(x, y).step(dir) {
+                        let (dx, dy) = (nx.abs_diff(x), ny.abs_diff(y));
+                        assert!(
+                            (dx, dy) == (1, 2) || (dx, dy) == (2, 1),
+                            "{dir:?} from ({x}, {y}) wrapped to ({nx}, {ny})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    ///Knight jumps which would cross an edge give `None`
+    #[test]
+    fn knight_jumps_off_the_edges() {
+        for y in 0..8 {
+            assert_eq!(Coords::OnBoard(7, y).step(Direction::EastNorthEast), None);
+            assert_eq!(Coords::OnBoard(7, y).step(Direction::SouthSouthEast), None);
+            assert_eq!(Coords::OnBoard(0, y).step(Direction::WestSouthWest), None);
+            assert_eq!(Coords::OnBoard(0, y).step(Direction::NorthNorthWest), None);
+        }
+        for x in 0..8 {
+            assert_eq!(Coords::OnBoard(x, 0).step(Direction::NorthNorthEast), None);
+            assert_eq!(Coords::OnBoard(x, 0).step(Direction::WestNorthWest), None);
+            assert_eq!(Coords::OnBoard(x, 7).step(Direction::SouthSouthWest), None);
+            assert_eq!(Coords::OnBoard(x, 7).step(Direction::EastSouthEast), None);
+        }
+    }
+
+    ///Knights in corners and on edges have exactly as many jumps as they should
+    #[test]
+    fn knight_target_counts_on_edges_and_corners() {
+        for corner in [(0_u8, 0_u8), (7, 0), (0, 7), (7, 7)] {
+            assert_eq!(knight_targets(corner.into()).len(), 2, "corner {corner:?}");
+        }
+
+        //next to a corner along an edge, and the middle of each edge
+        for square in [(1_u8, 0_u8), (0, 1), (6, 7), (7, 6)] {
+            assert_eq!(knight_targets(square.into()).len(), 3, "square {square:?}");
+        }
+        for square in [(3_u8, 0_u8), (0, 3), (4, 7), (7, 4)] {
+            assert_eq!(knight_targets(square.into()).len(), 4, "square {square:?}");
+        }
+
+        let mut h1 = knight_targets(Coords::OnBoard(7, 7));
+        h1.sort_by_key(Coords::to_usize);
+        assert_eq!(h1, vec![Coords::OnBoard(6, 5), Coords::OnBoard(5, 6)]);
+    }
+
+    ///Jumps from the h-file only ever land on the f or g files
+    #[test]
+    fn h_file_knight_never_reaches_the_a_or_b_file() {
+        for y in 0..8 {
+            for target in knight_targets(Coords::OnBoard(7, y)) {
+                assert!(matches!(target, Coords::OnBoard(5 | 6, _)), "{target:?}");
+            }
+        }
+    }
+
+    ///Each sliding direction moves exactly one square, in the direction its name says
+    #[test]
+    fn sliding_offsets() {
+        use Direction::{East, North, NorthEast, NorthWest, South, SouthEast, SouthWest, West};
+
+        let expected = [
+            (North, (0, -1)),
+            (NorthEast, (1, -1)),
+            (East, (1, 0)),
+            (SouthEast, (1, 1)),
+            (South, (0, 1)),
+            (SouthWest, (-1, 1)),
+            (West, (-1, 0)),
+            (NorthWest, (-1, -1)),
+        ];
+        assert_eq!(Direction::sliding().len(), expected.len());
+        for (dir, (expected_dir, offset)) in Direction::sliding().into_iter().zip(expected) {
+            assert_eq!(dir, expected_dir);
+            assert_eq!(dir.offset(), offset, "{dir:?}");
+        }
+    }
+
+    ///Only the four sliding directions which change both x and y are diagonal
+    #[test]
+    fn diagonals() {
+        for dir in Direction::sliding() {
+            let (dx, dy) = dir.offset();
+            assert_eq!(dir.is_diagonal(), dx != 0 && dy != 0, "{dir:?}");
+        }
+        for dir in Direction::knight_jumps() {
+            assert!(!dir.is_diagonal(), "{dir:?}");
+        }
+    }
+
+    ///Steps off each edge leave the board, and steps along or away from it don't
+    #[test]
+    fn stays_on_board_at_edges_and_corners() {
+        use Direction::{East, North, NorthEast, NorthWest, South, SouthEast, SouthWest, West};
+
+        for i in 0..8 {
+            assert!(!North.stays_on_board(Coords::OnBoard(i, 0)));
+            assert!(!East.stays_on_board(Coords::OnBoard(7, i)));
+            assert!(!South.stays_on_board(Coords::OnBoard(i, 7)));
+            assert!(!West.stays_on_board(Coords::OnBoard(0, i)));
+        }
+        assert!(South.stays_on_board(Coords::OnBoard(3, 0)));
+        assert!(West.stays_on_board(Coords::OnBoard(7, 3)));
+
+        //a8, h8, h1 and a1 can only go inwards
+        let corners = [
+            ((0, 0), [East, SouthEast, South]),
+            ((7, 0), [South, SouthWest, West]),
+            ((7, 7), [West, NorthWest, North]),
+            ((0, 7), [North, NorthEast, East]),
+        ];
+        for (corner, inwards) in corners {
+            let corner = Coords::from(corner);
+            for dir in Direction::sliding() {
+                assert_eq!(
+                    dir.stays_on_board(corner),
+                    inwards.contains(&dir),
+                    "{dir:?} from {corner:?}"
+                );
+            }
+        }
+
+        assert!(!North.stays_on_board(Coords::OffBoard));
+    }
+
+    ///Every square's name parses back to the same square
+    #[test]
+    fn names_round_trip() {
+        for x in 0..8 {
+            for y in 0..8 {
+                let square = Coords::OnBoard(x, y);
+                let name = square.to_name().unwrap();
+                assert_eq!(Coords::try_from(name.as_str()).unwrap(), square, "{name}");
+            }
+        }
+
+        assert_eq!(Coords::OnBoard(0, 0).to_name().as_deref(), Some("a8"));
+        assert_eq!(Coords::OnBoard(7, 7).to_name().as_deref(), Some("h1"));
+        assert_eq!(Coords::OffBoard.to_name(), None);
+    }
+}
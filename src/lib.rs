@@ -25,13 +25,15 @@ extern crate epac_utils;
 pub mod chess;
 ///Module to hold all networking modules
 pub mod net;
+///Module to set up logging and tracing - [`logging::init_tracing`]
+pub mod logging;
 
 ///Module to hold commonly used structs, enums and functions that should be in a prelude
 pub mod prelude {
     pub use crate::{
         chess::{
             chess_piece::{ChessPiece, ChessPieceKind},
-            coords::Coords,
+            coords::{Coords, Direction},
         },
     };
     pub use anyhow::{Error, Result};
@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Result};
+use std::{
+    env::{set_var, var},
+    sync::OnceLock,
+};
+use tracing::Level;
+use tracing_subscriber::{
+    filter::LevelFilter, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt,
+    EnvFilter, Registry,
+};
+use tracing_tree::HierarchicalLayer;
+
+///What the first call to [`init_tracing`] returned, so the global subscriber only gets set up once and later calls can report the same result
+///
+/// The error is stored as its message, as [`anyhow::Error`] can't be cloned
+static INIT: OnceLock<Result<(), String>> = OnceLock::new();
+
+///Function to setup all of the logging and tracing for the program
+///
+/// - Firstly, it sets the environment variable `RUST_LIB_BACKTRACE` to `1` if it isn't set
+/// - Then it sets up an Environment tracing logger with Tracing Tree, using `level` unless `RUST_LOG` says otherwise
+///
+/// Only the first call does anything - any later calls don't touch the subscriber, and return the same result as the first call (with the error as a message)
+///
+/// # Errors
+/// Can return an error if the `RUST_LOG` filter can't be parsed, or if the tracing [`Registry`] fails to initialise, and this happens when:
+/// > `This method returns an error if a global default subscriber has already been set, or if a log logger has already been set (when the "tracing-log" feature is enabled).`
+pub fn init_tracing(level: Level) -> Result<()> {
+    INIT.get_or_init(|| setup(level).map_err(|e| format!("{e:#}")))
+        .clone()
+        .map_err(|e| anyhow!(e))
+}
+
+///Sets up the subscriber for [`init_tracing`] - must only be called once
+fn setup(level: Level) -> Result<()> {
+    if var("RUST_LIB_BACKTRACE").is_err() {
+        println!("Setting RUST_LIB_BACKTRACE to 1");
+        set_var("RUST_LIB_BACKTRACE", "1");
+    }
+
+    Registry::default()
+        .with(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::from_level(level).into())
+                .from_env()?,
+        )
+        .with(
+            HierarchicalLayer::new(1)
+                .with_targets(true)
+                .with_bracketed_fields(true)
+                .with_verbose_entry(true)
+                .with_ansi(true),
+        )
+        .try_init()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+///Tests for setting up tracing
+mod tests {
+    use super::init_tracing;
+    use tracing::Level;
+
+    ///Calling [`init_tracing`] again doesn't panic, and gives back the first result
+    #[test]
+    fn repeated_init_gives_the_first_result() {
+        let first = init_tracing(Level::INFO).map_err(|e| e.to_string());
+        for level in [Level::INFO, Level::DEBUG, Level::TRACE] {
+            assert_eq!(init_tracing(level).map_err(|e| e.to_string()), first);
+        }
+    }
+}
@@ -6,11 +6,14 @@ use reqwest::{
 use std::{
     sync::{
         atomic::{AtomicBool, Ordering},
-        mpsc::{channel, Receiver, SendError, Sender, TryRecvError},
+        mpsc::{
+            channel, sync_channel, Receiver, SendError, Sender, SyncSender, TryRecvError,
+            TrySendError,
+        },
         Arc, Mutex,
     },
     thread::JoinHandle,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use epac_utils::either::Either;
 use epac_utils::error_ext::{ErrorExt, MutexExt, ToAnyhowThreadErr};
@@ -74,18 +77,74 @@ pub enum MoveOutcome {
     Worked(bool),
     ///The move is invalid, and should be undone
     Invalid,
-    ///The request from `reqwest` failed
+    ///The request from `reqwest` failed, or the move was dropped by the [`MoveThrottle`] - in which case no [`BoardMessage::TmpMove`] was sent first
     CouldntProcessMove,
 }
 
+///Settings for a [`ListRefresher`]
+#[derive(Debug, Copy, Clone)]
+pub struct ListRefresherSettings {
+    ///The most messages which can be waiting for the main thread before [`ListRefresher::send_msg`] fails with [`TrySendError::Full`]
+    pub queue_capacity: usize,
+    ///The shortest time allowed between moves - any [`MessageToWorker::MakeMove`] sent sooner than this is dropped, and [`MoveOutcome::CouldntProcessMove`] is sent back
+    pub min_move_interval: Duration,
+}
+
+impl Default for ListRefresherSettings {
+    fn default() -> Self {
+        Self {
+            queue_capacity: 32,
+            min_move_interval: Duration::from_millis(250),
+        }
+    }
+}
+
+///Stops moves being spammed, by only allowing one every [`ListRefresherSettings::min_move_interval`]
+#[derive(Debug, Copy, Clone)]
+struct MoveThrottle {
+    ///The shortest time allowed between two moves
+    min_interval: Duration,
+    ///When the last allowed move was made, or `None` if there hasn't been one
+    last: Option<Instant>,
+}
+
+impl MoveThrottle {
+    ///Creates a throttle which lets the first move through straight away
+    const fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last: None,
+        }
+    }
+
+    ///Checks whether a move made at `now` is allowed, and if it is, counts it as the last move
+    fn allow(&mut self, now: Instant) -> bool {
+        match self.last {
+            Some(last) if now.saturating_duration_since(last) < self.min_interval => false,
+            _ => {
+                self.last = Some(now);
+                true
+            }
+        }
+    }
+}
+
 ///Struct to refresh the board and deal with requests to the server, using multi-threading and channels
+///
+/// # Threading
+/// - [`ListRefresher::new`] spawns a main thread running [`run_loop`], which blocks on the [`MessageToWorker`] channel
+/// - Each request to the server is made from its own short-lived thread, so a slow request never stops the main thread taking messages
+/// - Every reply goes back through one [`MessageToGame`] channel, which is only FIFO per sending thread. Replies from different request threads arrive in the order the requests finish rather than the order they were made, so a stale [`BoardMessage::NewList`] can land between a [`BoardMessage::TmpMove`] and its [`BoardMessage::Move`]
+/// - The game should call [`ListRefresher::try_recv`] once a frame - it never blocks, so it is safe to call from the render loop
+///
+/// Dropping the `ListRefresher` joins the main thread, so [`ListRefresher::shutdown`] must be called first to end the loop.
 pub struct ListRefresher {
     ///Handle to hold the main thread.
     ///
     ///It is an `Option` because that makes it ownable for [`Drop::drop`] using [`std::mem::take`] as you need to own a [`JoinHandle`] to [`JoinHandle::join`] it to receive any errors.
     handle: Option<JoinHandle<()>>,
-    ///Sender to send messages to the main thread
-    tx: Sender<MessageToWorker>,
+    ///Sender to send messages to the main thread - bounded so messages can't pile up if the main thread stalls
+    tx: SyncSender<MessageToWorker>,
     ///Receiver for messages sent from the main thread to send them to the game.
     rx: Receiver<MessageToGame>,
 }
@@ -100,6 +159,7 @@ fn run_loop(
     mtw_rx: Receiver<MessageToWorker>,
     mtg_tx: Sender<MessageToGame>,
     id: u32,
+    settings: ListRefresherSettings,
 ) -> Result<()> {
     let update_req_inflight = Arc::new(AtomicBool::new(false));
     let move_req_inflight = Arc::new(AtomicBool::new(false));
@@ -116,6 +176,7 @@ fn run_loop(
 
     let request_timer = Arc::new(Mutex::new(MemoryTimedCacher::<_, 150>::new(None))); //cacher for printing av requests ttr
     let mut request_print_timer = DoOnInterval::new(Duration::from_millis(2500)); //timer for when to print av request ttr
+    let mut move_throttle = MoveThrottle::new(settings.min_move_interval);
 
     while let Ok(msg) = mtw_rx.recv() {
         {
@@ -194,6 +255,17 @@ fn run_loop(
                 });
             }
             MessageToWorker::MakeMove(m) => {
+                if !move_throttle.allow(Instant::now()) {
+                    warn!(?m, "Dropping move sent too soon after the last one");
+                    mtg_tx
+                        .send(MessageToGame::UpdateBoard(BoardMessage::Move(
+                            MoveOutcome::CouldntProcessMove,
+                        )))
+                        .context("dropped move result")
+                        .warn();
+                    continue;
+                }
+
                 let (mtg_tx, client, rt, mr_inflight) = (
                     mtg_tx.clone(),
                     client.clone(),
@@ -231,14 +303,20 @@ fn run_loop(
 }
 
 impl ListRefresher {
-    ///Create a new `ListRefresher`, and start up the main thread
+    ///Create a new `ListRefresher` with the default [`ListRefresherSettings`], and start up the main thread
     #[must_use]
     pub fn new(id: u32) -> Self {
-        let (mtw_tx, mtw_rx) = channel();
+        Self::with_settings(id, ListRefresherSettings::default())
+    }
+
+    ///Create a new `ListRefresher` using the given [`ListRefresherSettings`], and start up the main thread
+    #[must_use]
+    pub fn with_settings(id: u32, settings: ListRefresherSettings) -> Self {
+        let (mtw_tx, mtw_rx) = sync_channel(settings.queue_capacity);
         let (mtg_tx, mtg_rx) = channel();
 
         let thread = std::thread::spawn(move || {
-            run_loop(mtw_rx, mtg_tx, id)
+            run_loop(mtw_rx, mtg_tx, id, settings)
                 .context("error running refresh loop")
                 .error();
         });
@@ -250,12 +328,23 @@ impl ListRefresher {
         }
     }
 
-    ///Sends a message to the main thread
+    ///Sends a message to the main thread without blocking
+    ///
+    /// Shouldn't be used for [`MessageToWorker::InvalidateKill`], as that would get dropped if the queue is full - use [`ListRefresher::shutdown`] instead
+    ///
+    /// # Errors
+    /// - [`TrySendError::Full`] if there are already [`ListRefresherSettings::queue_capacity`] messages waiting
+    /// - [`TrySendError::Disconnected`] if the main thread has stopped
+    pub fn send_msg(&self, m: MessageToWorker) -> Result<(), TrySendError<MessageToWorker>> {
+        self.tx.try_send(m)
+    }
+
+    ///Sends [`MessageToWorker::InvalidateKill`] to end the main thread, blocking until there is space in the queue so that it can never be dropped
     ///
     /// # Errors
-    /// Can error if there is an error sending the message
-    pub fn send_msg(&self, m: MessageToWorker) -> Result<(), SendError<MessageToWorker>> {
-        self.tx.send(m)
+    /// - [`SendError`] if the main thread has already stopped
+    pub fn shutdown(&self) -> Result<(), SendError<MessageToWorker>> {
+        self.tx.send(MessageToWorker::InvalidateKill)
     }
     ///Tries to receive a message from the main thread in a non-blocking fashion
     ///
@@ -424,3 +513,70 @@ impl Drop for ListRefresher {
         }
     }
 }
+
+#[cfg(test)]
+///Tests for the channels and throttling used by [`ListRefresher`]
+mod tests {
+    use super::{ListRefresher, MessageToWorker, MoveThrottle};
+    use std::{
+        sync::mpsc::{channel, sync_channel, TrySendError},
+        time::{Duration, Instant},
+    };
+
+    ///Once the queue holds its capacity of messages, [`ListRefresher::send_msg`] fails with [`TrySendError::Full`] rather than blocking
+    #[test]
+    fn full_queue_is_reported() {
+        //no main thread, so nothing drains the queue
+        let (tx, worker_rx) = sync_channel(2);
+        let (_game_tx, rx) = channel();
+        let refresher = ListRefresher {
+            handle: None,
+            tx,
+            rx,
+        };
+
+        assert_eq!(refresher.send_msg(MessageToWorker::UpdateList), Ok(()));
+        assert_eq!(refresher.send_msg(MessageToWorker::UpdateNOW), Ok(()));
+        assert_eq!(
+            refresher.send_msg(MessageToWorker::RestartBoard),
+            Err(TrySendError::Full(MessageToWorker::RestartBoard))
+        );
+
+        assert_eq!(worker_rx.recv(), Ok(MessageToWorker::UpdateList));
+        assert_eq!(refresher.send_msg(MessageToWorker::RestartBoard), Ok(()));
+
+        drop(worker_rx);
+        assert_eq!(
+            refresher.send_msg(MessageToWorker::UpdateList),
+            Err(TrySendError::Disconnected(MessageToWorker::UpdateList))
+        );
+        assert!(refresher.shutdown().is_err());
+    }
+
+    ///The first move is always allowed, and after that only moves at least the interval apart are
+    #[test]
+    fn throttle_spaces_out_moves() {
+        let interval = Duration::from_millis(250);
+        let mut throttle = MoveThrottle::new(interval);
+        let start = Instant::now();
+
+        assert!(throttle.allow(start));
+        assert!(!throttle.allow(start));
+        assert!(!throttle.allow(start + interval - Duration::from_millis(1)));
+        assert!(throttle.allow(start + interval));
+
+        //dropped moves don't push the next allowed time back
+        assert!(!throttle.allow(start + interval + Duration::from_millis(100)));
+        assert!(throttle.allow(start + interval * 2));
+    }
+
+    ///A throttle with no interval lets everything through
+    #[test]
+    fn zero_interval_never_throttles() {
+        let mut throttle = MoveThrottle::new(Duration::ZERO);
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert!(throttle.allow(now));
+        }
+    }
+}
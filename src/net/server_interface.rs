@@ -1,12 +1,12 @@
 use crate::{
     chess::boards::board::{Board, CanMovePiece},
-    prelude::{ChessPiece, ChessPieceKind, Coords, Error, ErrorExt, Result},
+    prelude::{ChessPiece, ChessPieceKind, Coords, Error, ErrorExt, Result, SError},
     util::error_ext::ToAnyhowNotErr,
 };
 use anyhow::Context;
 use epac_utils::error_ext::{ErrorExt, ToAnyhowNotErr};
 use serde::{Deserialize, Serialize};
-use strum::IntoEnumIterator;
+use strum::{Display, IntoEnumIterator};
 
 ///Unit struct to hold a vector of [`JSONPiece`]s.
 #[derive(Deserialize, Debug, Default)]
@@ -137,6 +137,19 @@ pub fn no_connection_list() -> Board<CanMovePiece> {
         .unwrap_log_error()
 }
 
+///Enum to hold errors from parsing a UCI move like `e2e4`
+#[derive(Debug, Display)]
+pub enum UciParseError {
+    ///The move isn't 4 characters long, or 5 with a promotion
+    WrongLength(String),
+    ///The move promotes to something other than a queen, which the server doesn't support
+    UnsupportedPromotion(String),
+    ///One of the squares isn't a valid square name
+    InvalidSquare(String),
+}
+
+impl SError for UciParseError {}
+
 ///JSON repr of a chess move
 #[derive(Serialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct JSONMove {
@@ -159,6 +172,51 @@ impl JSONMove {
         Self { id, x, y, nx, ny }
     }
 
+    ///Parses a UCI move like `e2e4` for the game with the given id
+    ///
+    /// A trailing `q` for a queen promotion is allowed, as the server always promotes to a queen
+    ///
+    /// # Errors
+    /// - [`UciParseError::WrongLength`] if the move isn't 4 or 5 characters long
+    /// - [`UciParseError::UnsupportedPromotion`] if the move promotes to anything but a queen
+    /// - [`UciParseError::InvalidSquare`] if either square can't be parsed into [`Coords`]
+    pub fn from_uci(id: u32, uci: &str) -> Result<Self, UciParseError> {
+        let uci = uci.trim().to_lowercase();
+        match uci.len() {
+            4 => {}
+            5 if uci.ends_with('q') => {}
+            5 => return Err(UciParseError::UnsupportedPromotion(uci)),
+            _ => return Err(UciParseError::WrongLength(uci)),
+        }
+
+        let square = |range: std::ops::Range<usize>| {
+            uci.get(range)
+                .and_then(|name| Coords::try_from(name).ok())
+                .and_then(|c| c.to_option())
+                .ok_or_else(|| UciParseError::InvalidSquare(uci.clone()))
+        };
+        let (x, y) = square(0..2)?;
+        let (nx, ny) = square(2..4)?;
+
+        Ok(Self::new(
+            id,
+            u32::from(x),
+            u32::from(y),
+            u32::from(nx),
+            u32::from(ny),
+        ))
+    }
+
+    ///Formats the move as a UCI move like `e2e4`, or `None` if either square is off the board
+    ///
+    /// A `JSONMove` doesn't hold a promotion, as the server always promotes to a queen, so the `q` from [`Self::from_uci`] is lost - `e7e8q` comes back as `e7e8`
+    #[must_use]
+    pub fn to_uci(&self) -> Option<String> {
+        let from = Coords::try_from((self.x, self.y)).ok()?.to_name()?;
+        let to = Coords::try_from((self.nx, self.ny)).ok()?.to_name()?;
+        Some(from + &to)
+    }
+
     ///Gets the starting coordinates as a [`Coords`]
     #[must_use]
     pub fn current_coords(&self) -> Coords {
@@ -170,3 +228,35 @@ impl JSONMove {
         (self.nx, self.ny).try_into().unwrap_log_error()
     }
 }
+
+#[cfg(test)]
+///Tests for converting [`JSONMove`]s to and from UCI
+mod tests {
+    use super::JSONMove;
+
+    ///A normal move comes back exactly as it was parsed
+    #[test]
+    fn normal_move_round_trips() {
+        let m = JSONMove::from_uci(3, "e2e4").unwrap();
+        assert_eq!(m, JSONMove::new(3, 4, 6, 4, 4));
+        assert_eq!(m.to_uci().as_deref(), Some("e2e4"));
+        assert_eq!(JSONMove::from_uci(3, &m.to_uci().unwrap()).unwrap(), m);
+    }
+
+    ///A promotion parses to the same move as without the `q`, so it comes back without it
+    #[test]
+    fn promotion_move_round_trips() {
+        let m = JSONMove::from_uci(3, "e7e8q").unwrap();
+        assert_eq!(m, JSONMove::from_uci(3, "e7e8").unwrap());
+        assert_eq!(m.to_uci().as_deref(), Some("e7e8"));
+        assert_eq!(JSONMove::from_uci(3, &m.to_uci().unwrap()).unwrap(), m);
+        assert!(JSONMove::from_uci(3, "e7e8n").is_err());
+    }
+
+    ///Moves with a square off the board have no UCI form
+    #[test]
+    fn off_board_move_has_no_uci() {
+        assert_eq!(JSONMove::new(3, 8, 0, 0, 0).to_uci(), None);
+        assert_eq!(JSONMove::new(3, 0, 0, 0, 8).to_uci(), None);
+    }
+}